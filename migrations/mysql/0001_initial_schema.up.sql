@@ -1,18 +1,18 @@
-CREATE TABLE admins (
+CREATE TABLE IF NOT EXISTS admins (
     admin_id INT PRIMARY KEY AUTO_INCREMENT,
     username VARCHAR(50) UNIQUE NOT NULL,
     password_hash CHAR(60) NOT NULL,
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
 );
 
-CREATE TABLE players (
+CREATE TABLE IF NOT EXISTS players (
     player_id INT PRIMARY KEY AUTO_INCREMENT,
     username VARCHAR(50) UNIQUE NOT NULL,
     password_hash CHAR(60) NOT NULL,
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
 );
 
-CREATE TABLE games (
+CREATE TABLE IF NOT EXISTS games (
     game_id INT PRIMARY KEY AUTO_INCREMENT,
     title VARCHAR(100) UNIQUE NOT NULL,
     description TEXT,
@@ -21,7 +21,7 @@ CREATE TABLE games (
     FOREIGN KEY (created_by) REFERENCES admins (admin_id) ON DELETE SET NULL
 );
 
-CREATE TABLE questions (
+CREATE TABLE IF NOT EXISTS questions (
     question_id INT PRIMARY KEY AUTO_INCREMENT,
     question_text TEXT NOT NULL,
     correct_answer BOOLEAN NOT NULL,
@@ -30,7 +30,7 @@ CREATE TABLE questions (
     FOREIGN KEY (created_by) REFERENCES admins (admin_id) ON DELETE SET NULL
 );
 
-CREATE TABLE game_questions (
+CREATE TABLE IF NOT EXISTS game_questions (
     game_id INT,
     question_id INT,
     question_order INT NOT NULL,
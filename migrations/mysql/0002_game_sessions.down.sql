@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS session_answers;
+DROP TABLE IF EXISTS game_sessions;
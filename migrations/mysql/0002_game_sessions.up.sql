@@ -0,0 +1,23 @@
+CREATE TABLE game_sessions (
+    session_id INT PRIMARY KEY AUTO_INCREMENT,
+    player_id INT NOT NULL,
+    game_id INT NOT NULL,
+    started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ended_at TIMESTAMP NULL,
+    correct_answers INT NOT NULL DEFAULT 0,
+    total_questions INT NOT NULL DEFAULT 0,
+    FOREIGN KEY (player_id) REFERENCES players (player_id) ON DELETE CASCADE,
+    FOREIGN KEY (game_id) REFERENCES games (game_id) ON DELETE CASCADE
+);
+
+CREATE TABLE session_answers (
+    answer_id INT PRIMARY KEY AUTO_INCREMENT,
+    session_id INT NOT NULL,
+    question_id INT NOT NULL,
+    submitted_answer VARCHAR(255) NOT NULL,
+    is_correct BOOLEAN NOT NULL,
+    response_time_ms INT NOT NULL,
+    answered_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (session_id) REFERENCES game_sessions (session_id) ON DELETE CASCADE,
+    FOREIGN KEY (question_id) REFERENCES questions (question_id) ON DELETE CASCADE
+);
@@ -0,0 +1,12 @@
+ALTER TABLE questions
+    ADD COLUMN kind VARCHAR(20) NOT NULL DEFAULT 'true_false';
+
+CREATE TABLE question_choices (
+    choice_id INT PRIMARY KEY AUTO_INCREMENT,
+    question_id INT NOT NULL,
+    choice_order INT NOT NULL,
+    choice_text TEXT NOT NULL,
+    is_correct BOOLEAN NOT NULL DEFAULT FALSE,
+    UNIQUE (question_id, choice_order),
+    FOREIGN KEY (question_id) REFERENCES questions (question_id) ON DELETE CASCADE
+);
@@ -0,0 +1,7 @@
+CREATE TABLE accepted_answers (
+    accepted_answer_id INT PRIMARY KEY AUTO_INCREMENT,
+    question_id INT NOT NULL,
+    answer_text TEXT NOT NULL,
+    max_distance INT NOT NULL DEFAULT 0,
+    FOREIGN KEY (question_id) REFERENCES questions (question_id) ON DELETE CASCADE
+);
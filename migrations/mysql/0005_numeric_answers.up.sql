@@ -0,0 +1,8 @@
+CREATE TABLE numeric_answers (
+    question_id INT PRIMARY KEY,
+    target_value DOUBLE NOT NULL,
+    tolerance DOUBLE NOT NULL DEFAULT 0,
+    tolerance_percent BOOLEAN NOT NULL DEFAULT FALSE,
+    unit VARCHAR(20),
+    FOREIGN KEY (question_id) REFERENCES questions (question_id) ON DELETE CASCADE
+);
@@ -0,0 +1,21 @@
+ALTER TABLE questions
+    ADD COLUMN partial_credit BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE ordering_items (
+    item_id INT PRIMARY KEY AUTO_INCREMENT,
+    question_id INT NOT NULL,
+    item_order INT NOT NULL,
+    item_text TEXT NOT NULL,
+    UNIQUE (question_id, item_order),
+    FOREIGN KEY (question_id) REFERENCES questions (question_id) ON DELETE CASCADE
+);
+
+-- Partially correct answers earn a fraction of a point, so scores are no
+-- longer whole numbers of correct answers.
+ALTER TABLE session_answers
+    ADD COLUMN score DOUBLE NOT NULL DEFAULT 0;
+UPDATE session_answers SET score = is_correct;
+
+ALTER TABLE game_sessions
+    ADD COLUMN score DOUBLE NOT NULL DEFAULT 0;
+UPDATE game_sessions SET score = correct_answers;
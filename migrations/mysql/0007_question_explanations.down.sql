@@ -0,0 +1,3 @@
+ALTER TABLE questions
+    DROP COLUMN source,
+    DROP COLUMN explanation;
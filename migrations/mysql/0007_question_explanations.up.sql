@@ -0,0 +1,3 @@
+ALTER TABLE questions
+    ADD COLUMN explanation TEXT NULL,
+    ADD COLUMN source TEXT NULL;
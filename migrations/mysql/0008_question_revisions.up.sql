@@ -0,0 +1,14 @@
+-- Each row holds a question as it was before one edit. `answer` is the
+-- kind-specific answer encoded as JSON, so any kind can be restored.
+CREATE TABLE question_revisions (
+    revision_id INT PRIMARY KEY AUTO_INCREMENT,
+    question_id INT NOT NULL,
+    question_text TEXT NOT NULL,
+    answer TEXT NOT NULL,
+    explanation TEXT NULL,
+    source TEXT NULL,
+    edited_by INT,
+    edited_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (question_id) REFERENCES questions (question_id) ON DELETE CASCADE,
+    FOREIGN KEY (edited_by) REFERENCES admins (admin_id) ON DELETE SET NULL
+);
@@ -0,0 +1,8 @@
+-- Anything still in the trash would reappear, so purge it first.
+DELETE FROM questions WHERE deleted_at IS NOT NULL;
+DELETE FROM games WHERE deleted_at IS NOT NULL;
+DELETE FROM players WHERE deleted_at IS NOT NULL;
+
+ALTER TABLE questions DROP COLUMN deleted_at;
+ALTER TABLE games DROP COLUMN deleted_at;
+ALTER TABLE players DROP COLUMN deleted_at;
@@ -0,0 +1,5 @@
+-- Deleting a player, game or question sets `deleted_at` instead of removing
+-- the row, so it can be restored from the trash until it is purged.
+ALTER TABLE players ADD COLUMN deleted_at TIMESTAMP NULL;
+ALTER TABLE games ADD COLUMN deleted_at TIMESTAMP NULL;
+ALTER TABLE questions ADD COLUMN deleted_at TIMESTAMP NULL;
@@ -0,0 +1 @@
+ALTER TABLE questions DROP INDEX ft_questions_text;
@@ -0,0 +1,3 @@
+-- Backs keyword search over the question bank.
+ALTER TABLE questions
+    ADD FULLTEXT INDEX ft_questions_text (question_text, explanation);
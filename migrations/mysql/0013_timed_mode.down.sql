@@ -0,0 +1,6 @@
+-- Timed scores can include speed bonuses, so they would skew the regular
+-- leaderboards once the mode is gone.
+DELETE FROM game_sessions WHERE mode <> 'regular';
+ALTER TABLE game_sessions DROP COLUMN mode;
+
+ALTER TABLE questions DROP COLUMN time_limit_secs;
@@ -0,0 +1,15 @@
+-- One finished survival run. Runs through the shuffled pool of every game
+-- have no game_id.
+CREATE TABLE survival_runs (
+    run_id INT PRIMARY KEY AUTO_INCREMENT,
+    player_id INT NOT NULL,
+    game_id INT NULL,
+    lives INT NOT NULL,
+    questions_answered INT NOT NULL,
+    correct_answers INT NOT NULL,
+    score DOUBLE NOT NULL,
+    played_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (player_id) REFERENCES players (player_id) ON DELETE CASCADE,
+    FOREIGN KEY (game_id) REFERENCES games (game_id) ON DELETE CASCADE,
+    INDEX idx_survival_runs_board (game_id, lives, correct_answers)
+);
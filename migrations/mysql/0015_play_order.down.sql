@@ -0,0 +1,4 @@
+ALTER TABLE game_sessions DROP COLUMN seed;
+
+ALTER TABLE games DROP COLUMN draw_count;
+ALTER TABLE games DROP COLUMN play_order;
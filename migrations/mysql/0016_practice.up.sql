@@ -0,0 +1,16 @@
+-- A player's SM-2 schedule for a question they have missed. Cards are made
+-- the first time a missed question is practiced; until then the miss in
+-- session_answers is what puts it in the practice queue.
+CREATE TABLE practice_cards (
+    player_id INT NOT NULL,
+    question_id INT NOT NULL,
+    repetitions INT NOT NULL,
+    ease_factor DOUBLE NOT NULL,
+    interval_days INT NOT NULL,
+    due_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    reviewed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (player_id, question_id),
+    FOREIGN KEY (player_id) REFERENCES players (player_id) ON DELETE CASCADE,
+    FOREIGN KEY (question_id) REFERENCES questions (question_id) ON DELETE CASCADE,
+    INDEX idx_practice_cards_due (player_id, due_at)
+);
@@ -0,0 +1,3 @@
+-- max_score stays at the base points; it can't tell whether the bonus was on.
+UPDATE game_sessions SET score = score + bonus;
+ALTER TABLE game_sessions DROP COLUMN bonus;
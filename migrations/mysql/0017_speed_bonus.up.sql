@@ -0,0 +1,33 @@
+-- Speed bonuses are kept apart from the score, so score / max_score is the
+-- share of a game's points earned in either mode. Timed sessions saved before
+-- had the bonus folded into both, so their base points are worked out again
+-- from the answers.
+ALTER TABLE game_sessions
+    ADD COLUMN bonus DOUBLE NOT NULL DEFAULT 0;
+
+UPDATE game_sessions
+SET bonus = score - (
+    SELECT COALESCE(SUM(sa.score * CASE q.difficulty
+                                      WHEN 'easy' THEN 1
+                                      WHEN 'hard' THEN 3
+                                      ELSE 2
+                                  END), 0)
+    FROM session_answers sa
+    JOIN questions q ON q.question_id = sa.question_id
+    WHERE sa.session_id = game_sessions.session_id
+)
+WHERE mode = 'timed' AND ended_at IS NOT NULL;
+
+UPDATE game_sessions
+SET score = score - bonus,
+    max_score = (
+        SELECT COALESCE(SUM(CASE q.difficulty
+                                WHEN 'easy' THEN 1
+                                WHEN 'hard' THEN 3
+                                ELSE 2
+                            END), 0)
+        FROM session_answers sa
+        JOIN questions q ON q.question_id = sa.question_id
+        WHERE sa.session_id = game_sessions.session_id
+    )
+WHERE mode = 'timed' AND ended_at IS NOT NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE game_sessions DROP COLUMN draw_count;
+ALTER TABLE game_sessions DROP COLUMN play_order;
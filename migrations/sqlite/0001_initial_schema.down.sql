@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS game_questions;
+DROP TABLE IF EXISTS questions;
+DROP TABLE IF EXISTS games;
+DROP TABLE IF EXISTS players;
+DROP TABLE IF EXISTS admins;
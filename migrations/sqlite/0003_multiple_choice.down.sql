@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS question_choices;
+
+ALTER TABLE questions
+    DROP COLUMN kind;
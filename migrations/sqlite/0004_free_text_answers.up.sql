@@ -0,0 +1,7 @@
+CREATE TABLE accepted_answers (
+    accepted_answer_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    question_id INTEGER NOT NULL,
+    answer_text TEXT NOT NULL,
+    max_distance INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (question_id) REFERENCES questions (question_id) ON DELETE CASCADE
+);
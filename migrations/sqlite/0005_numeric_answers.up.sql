@@ -0,0 +1,8 @@
+CREATE TABLE numeric_answers (
+    question_id INTEGER PRIMARY KEY,
+    target_value REAL NOT NULL,
+    tolerance REAL NOT NULL DEFAULT 0,
+    tolerance_percent BOOLEAN NOT NULL DEFAULT FALSE,
+    unit VARCHAR(20),
+    FOREIGN KEY (question_id) REFERENCES questions (question_id) ON DELETE CASCADE
+);
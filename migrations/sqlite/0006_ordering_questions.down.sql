@@ -0,0 +1,10 @@
+ALTER TABLE game_sessions
+    DROP COLUMN score;
+
+ALTER TABLE session_answers
+    DROP COLUMN score;
+
+DROP TABLE IF EXISTS ordering_items;
+
+ALTER TABLE questions
+    DROP COLUMN partial_credit;
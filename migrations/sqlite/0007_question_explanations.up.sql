@@ -0,0 +1,2 @@
+ALTER TABLE questions ADD COLUMN explanation TEXT NULL;
+ALTER TABLE questions ADD COLUMN source TEXT NULL;
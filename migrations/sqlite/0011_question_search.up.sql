@@ -0,0 +1,28 @@
+-- Backs keyword search over the question bank. SQLite has no full-text
+-- indexes on ordinary tables, so an FTS5 table mirrors the searchable
+-- columns and triggers keep it in step with `questions`.
+CREATE VIRTUAL TABLE questions_fts USING fts5(
+    question_text,
+    explanation,
+    content = 'questions',
+    content_rowid = 'question_id'
+);
+
+CREATE TRIGGER questions_fts_insert AFTER INSERT ON questions BEGIN
+    INSERT INTO questions_fts (rowid, question_text, explanation)
+    VALUES (new.question_id, new.question_text, new.explanation);
+END;
+
+CREATE TRIGGER questions_fts_delete AFTER DELETE ON questions BEGIN
+    INSERT INTO questions_fts (questions_fts, rowid, question_text, explanation)
+    VALUES ('delete', old.question_id, old.question_text, old.explanation);
+END;
+
+CREATE TRIGGER questions_fts_update AFTER UPDATE OF question_text, explanation ON questions BEGIN
+    INSERT INTO questions_fts (questions_fts, rowid, question_text, explanation)
+    VALUES ('delete', old.question_id, old.question_text, old.explanation);
+    INSERT INTO questions_fts (rowid, question_text, explanation)
+    VALUES (new.question_id, new.question_text, new.explanation);
+END;
+
+INSERT INTO questions_fts (questions_fts) VALUES ('rebuild');
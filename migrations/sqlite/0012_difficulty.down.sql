@@ -0,0 +1,8 @@
+UPDATE game_sessions
+SET score = COALESCE(
+    (SELECT SUM(sa.score) FROM session_answers sa WHERE sa.session_id = game_sessions.session_id),
+    0
+);
+ALTER TABLE game_sessions DROP COLUMN max_score;
+
+ALTER TABLE questions DROP COLUMN difficulty;
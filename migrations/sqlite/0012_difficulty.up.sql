@@ -0,0 +1,9 @@
+ALTER TABLE questions
+    ADD COLUMN difficulty VARCHAR(10) NOT NULL DEFAULT 'medium';
+
+-- Sessions are now scored in points, weighted by difficulty. Every existing
+-- question is medium (2 points), so earlier sessions are rescaled to match.
+-- session_answers.score stays the share of the question's points earned.
+ALTER TABLE game_sessions
+    ADD COLUMN max_score REAL NOT NULL DEFAULT 0;
+UPDATE game_sessions SET score = score * 2, max_score = total_questions * 2;
@@ -0,0 +1,6 @@
+-- NULL means the question uses the default limit from config.toml.
+ALTER TABLE questions
+    ADD COLUMN time_limit_secs INT NULL;
+
+ALTER TABLE game_sessions
+    ADD COLUMN mode VARCHAR(10) NOT NULL DEFAULT 'regular';
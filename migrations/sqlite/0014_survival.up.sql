@@ -0,0 +1,16 @@
+-- One finished survival run. Runs through the shuffled pool of every game
+-- have no game_id.
+CREATE TABLE survival_runs (
+    run_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    player_id INTEGER NOT NULL,
+    game_id INTEGER NULL,
+    lives INTEGER NOT NULL,
+    questions_answered INTEGER NOT NULL,
+    correct_answers INTEGER NOT NULL,
+    score REAL NOT NULL,
+    played_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (player_id) REFERENCES players (player_id) ON DELETE CASCADE,
+    FOREIGN KEY (game_id) REFERENCES games (game_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_survival_runs_board ON survival_runs (game_id, lives, correct_answers);
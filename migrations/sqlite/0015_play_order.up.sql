@@ -0,0 +1,12 @@
+-- How a game's questions are put in order for each play: 'fixed' follows
+-- game_questions.question_order, 'shuffled' shuffles them and 'draw' plays
+-- draw_count of them picked at random.
+ALTER TABLE games
+    ADD COLUMN play_order VARCHAR(10) NOT NULL DEFAULT 'fixed';
+ALTER TABLE games
+    ADD COLUMN draw_count INT NULL;
+
+-- Seed of the shuffle a session was played with, so its order can be
+-- reproduced. NULL for sessions played in fixed order.
+ALTER TABLE game_sessions
+    ADD COLUMN seed BIGINT NULL;
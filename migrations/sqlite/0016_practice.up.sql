@@ -0,0 +1,17 @@
+-- A player's SM-2 schedule for a question they have missed. Cards are made
+-- the first time a missed question is practiced; until then the miss in
+-- session_answers is what puts it in the practice queue.
+CREATE TABLE practice_cards (
+    player_id INTEGER NOT NULL,
+    question_id INTEGER NOT NULL,
+    repetitions INTEGER NOT NULL,
+    ease_factor REAL NOT NULL,
+    interval_days INTEGER NOT NULL,
+    due_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    reviewed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (player_id, question_id),
+    FOREIGN KEY (player_id) REFERENCES players (player_id) ON DELETE CASCADE,
+    FOREIGN KEY (question_id) REFERENCES questions (question_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_practice_cards_due ON practice_cards (player_id, due_at);
@@ -0,0 +1,12 @@
+-- The play order a session used, next to its seed, so the session's question
+-- order can be rebuilt after the game's own setting has changed. Earlier
+-- sessions take the game's current setting, the best guess left.
+ALTER TABLE game_sessions
+    ADD COLUMN play_order VARCHAR(10) NOT NULL DEFAULT 'fixed';
+ALTER TABLE game_sessions
+    ADD COLUMN draw_count INT NULL;
+
+UPDATE game_sessions
+SET play_order = (SELECT g.play_order FROM games g WHERE g.game_id = game_sessions.game_id),
+    draw_count = (SELECT g.draw_count FROM games g WHERE g.game_id = game_sessions.game_id)
+WHERE seed IS NOT NULL;
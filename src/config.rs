@@ -4,12 +4,49 @@ use std::fs;
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
     pub database: DatabaseConfig,
+    #[serde(default)]
+    pub game: GameConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DatabaseConfig {
     pub url: String,
     pub max_connections: u32,
+    /// Apply pending schema migrations on start without asking first.
+    #[serde(default)]
+    pub auto_migrate: bool,
+}
+
+/// Settings for the timed and survival modes. The `[game]` table is optional.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameConfig {
+    /// Seconds allowed for a question that has no time limit of its own.
+    #[serde(default = "default_time_limit_secs")]
+    pub time_limit_secs: i32,
+    /// Award extra points for correct answers given with time to spare.
+    #[serde(default)]
+    pub speed_bonus: bool,
+    /// Wrong answers a survival run allows; 1 is sudden death.
+    #[serde(default = "default_survival_lives")]
+    pub survival_lives: i32,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            time_limit_secs: default_time_limit_secs(),
+            speed_bonus: false,
+            survival_lives: default_survival_lives(),
+        }
+    }
+}
+
+fn default_time_limit_secs() -> i32 {
+    30
+}
+
+fn default_survival_lives() -> i32 {
+    1
 }
 
 impl Config {
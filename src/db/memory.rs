@@ -0,0 +1,2415 @@
+use super::{
+    check_edit, check_question_order, normalize_tags, search_terms, validate_accepted_answers,
+    validate_choices, validate_new_question, validate_numeric_answer, validate_ordering_items,
+    validate_play_order, validate_time_limit, MigrationStatus, TriviaStore,
+};
+use crate::error::{Result, TriviaError};
+use crate::models::{
+    normalize_tag, AcceptedAnswer, Admin, Answer, AnswerSpec, Difficulty, Game, GameMode,
+    GameQuestion, GameQuestionFull, GameSession, GameUsage, LeaderboardBasis, LeaderboardEntry,
+    NewQuestion, NumericAnswer, OrderingItem, PlayOrder, Player, PracticeCard, Question,
+    QuestionChoice, QuestionKind, QuestionRevision, QuestionSearch, QuestionUsage, SessionAnswer,
+    SurvivalRun, SurvivalScore, Tag, TrashItem, TrashKind,
+};
+use crate::password::{hash_password, verify_password, PasswordCheck};
+use chrono::{DateTime, Utc};
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// A [`TriviaStore`] that keeps everything in process memory.
+///
+/// It enforces the same unique keys and cascades as the SQL schema, so the
+/// game behaves the same as it does against MariaDB.
+#[derive(Default)]
+pub struct MemoryStore {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    admins: Vec<Admin>,
+    players: Vec<Player>,
+    games: Vec<Game>,
+    questions: Vec<Question>,
+    question_choices: Vec<QuestionChoice>,
+    accepted_answers: Vec<AcceptedAnswer>,
+    numeric_answers: Vec<NumericAnswer>,
+    ordering_items: Vec<OrderingItem>,
+    question_revisions: Vec<QuestionRevision>,
+    /// `created_by` and `created_at` of each question.
+    question_created: HashMap<i32, (i32, DateTime<Utc>)>,
+    /// Tag names; a tag's id is its index plus one.
+    tags: Vec<String>,
+    /// `(question_id, tag_id)` pairs.
+    question_tags: Vec<(i32, i32)>,
+    /// `(game_id, tag_id)` pairs.
+    game_tags: Vec<(i32, i32)>,
+    game_questions: Vec<GameQuestion>,
+    sessions: Vec<GameSession>,
+    session_answers: Vec<SessionAnswer>,
+    survival_runs: Vec<SurvivalRun>,
+    practice_cards: Vec<PracticeCard>,
+    /// `deleted_at` of every player, game and question in the trash.
+    deleted: HashMap<(TrashKind, i32), DateTime<Utc>>,
+    next_admin_id: i32,
+    next_player_id: i32,
+    next_game_id: i32,
+    next_question_id: i32,
+    next_choice_id: i32,
+    next_accepted_answer_id: i32,
+    next_item_id: i32,
+    next_revision_id: i32,
+    next_session_id: i32,
+    next_answer_id: i32,
+    next_run_id: i32,
+}
+
+/// Running totals for one leaderboard row.
+#[derive(Clone, Copy)]
+struct Tally {
+    correct_answers: i64,
+    total_questions: i64,
+    score: f64,
+    max_score: f64,
+    bonus: f64,
+    time_ms: i64,
+    ended_at: DateTime<Utc>,
+    games_played: i64,
+}
+
+impl Tally {
+    /// Higher score with bonus first, then faster, then whoever finished
+    /// first.
+    fn rank_cmp(&self, other: &Tally) -> Ordering {
+        (other.score + other.bonus)
+            .total_cmp(&(self.score + self.bonus))
+            .then(self.time_ms.cmp(&other.time_ms))
+            .then(self.ended_at.cmp(&other.ended_at))
+    }
+}
+
+fn next_id(counter: &mut i32) -> i32 {
+    *counter += 1;
+    *counter
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an admin account. There is no admin sign-up flow in the CLI, so
+    /// this is how an in-memory store gets someone who can log in.
+    pub fn create_admin(&self, username: &str, password: &str) -> Result<i32> {
+        let password_hash = hash_password(password)?;
+        let mut state = self.state();
+
+        if state.admins.iter().any(|a| a.username == username) {
+            return Err(TriviaError::DuplicateUsername(username.to_string()));
+        }
+
+        let admin_id = next_id(&mut state.next_admin_id);
+        state.admins.push(Admin {
+            admin_id,
+            username: username.to_string(),
+            password_hash,
+            created_at: Some(Utc::now()),
+        });
+
+        Ok(admin_id)
+    }
+
+    /// Creates a store for a `memory:` database URL. Nothing is kept between
+    /// runs, so `memory://username:password@` also adds an admin to log in
+    /// with.
+    pub fn from_url(url: &str) -> Result<Self> {
+        let invalid = || TriviaError::InvalidInput(format!("'{}' is not a memory: URL", url));
+        let rest = url.strip_prefix("memory:").ok_or_else(invalid)?;
+        let store = Self::new();
+
+        if let Some((credentials, _)) = rest.trim_start_matches('/').split_once('@') {
+            let (username, password) = credentials.split_once(':').ok_or_else(invalid)?;
+            if username.is_empty() || password.is_empty() {
+                return Err(invalid());
+            }
+            store.create_admin(username, password)?;
+        }
+
+        Ok(store)
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        // A poisoned lock only means another caller panicked mid-update; the
+        // data is still usable for a CLI session.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl State {
+    /// Mirrors the SQL leaderboard queries: one finished session per player
+    /// per game is chosen by `basis`, then totals are ranked by score, total
+    /// response time and finish time.
+    fn leaderboard(
+        &self,
+        game_id: Option<i32>,
+        mode: GameMode,
+        basis: LeaderboardBasis,
+        limit: i32,
+    ) -> Vec<LeaderboardEntry> {
+        let mut chosen: HashMap<(i32, i32), Tally> = HashMap::new();
+        for session in &self.sessions {
+            let Some(ended_at) = session.ended_at else {
+                continue;
+            };
+            if game_id.is_some_and(|id| id != session.game_id)
+                || session.mode != mode
+                || self.is_deleted(TrashKind::Player, session.player_id)
+                || self.is_deleted(TrashKind::Game, session.game_id)
+            {
+                continue;
+            }
+
+            let candidate = Tally {
+                correct_answers: session.correct_answers as i64,
+                total_questions: session.total_questions as i64,
+                score: session.score,
+                max_score: session.max_score,
+                bonus: session.bonus,
+                time_ms: self
+                    .session_answers
+                    .iter()
+                    .filter(|a| a.session_id == session.session_id)
+                    .map(|a| a.response_time_ms as i64)
+                    .sum(),
+                ended_at,
+                games_played: 1,
+            };
+            chosen
+                .entry((session.player_id, session.game_id))
+                .and_modify(|current| {
+                    let better = match basis {
+                        LeaderboardBasis::Latest => candidate.ended_at > current.ended_at,
+                        LeaderboardBasis::Best => candidate.rank_cmp(current).is_lt(),
+                    };
+                    if better {
+                        *current = candidate;
+                    }
+                })
+                .or_insert(candidate);
+        }
+
+        let mut totals: HashMap<i32, Tally> = HashMap::new();
+        for ((player_id, _), tally) in chosen {
+            totals
+                .entry(player_id)
+                .and_modify(|t| {
+                    t.correct_answers += tally.correct_answers;
+                    t.total_questions += tally.total_questions;
+                    t.score += tally.score;
+                    t.max_score += tally.max_score;
+                    t.bonus += tally.bonus;
+                    t.time_ms += tally.time_ms;
+                    t.ended_at = t.ended_at.max(tally.ended_at);
+                    t.games_played += 1;
+                })
+                .or_insert(tally);
+        }
+
+        let mut rows: Vec<(&Player, Tally)> = totals
+            .into_iter()
+            .filter_map(|(player_id, tally)| {
+                self.players
+                    .iter()
+                    .find(|p| p.player_id == player_id)
+                    .map(|p| (p, tally))
+            })
+            .collect();
+        rows.sort_by(|(_, a), (_, b)| a.rank_cmp(b));
+
+        rows.into_iter()
+            .take(limit.max(0) as usize)
+            .enumerate()
+            .map(|(i, (player, tally))| LeaderboardEntry {
+                rank: i as i64 + 1,
+                player_id: player.player_id,
+                username: player.username.clone(),
+                correct_answers: tally.correct_answers,
+                total_questions: tally.total_questions,
+                score: tally.score,
+                max_score: tally.max_score,
+                bonus: tally.bonus,
+                time_ms: tally.time_ms,
+                games_played: tally.games_played,
+            })
+            .collect()
+    }
+
+    fn is_deleted(&self, kind: TrashKind, id: i32) -> bool {
+        self.deleted.contains_key(&(kind, id))
+    }
+
+    /// Moves a live row to the trash, like the SQL `UPDATE ... SET deleted_at`.
+    fn soft_delete(&mut self, kind: TrashKind, id: i32) -> Result<()> {
+        let exists = match kind {
+            TrashKind::Player => self.players.iter().any(|p| p.player_id == id),
+            TrashKind::Game => self.games.iter().any(|g| g.game_id == id),
+            TrashKind::Question => self.questions.iter().any(|q| q.question_id == id),
+        };
+        if !exists || self.is_deleted(kind, id) {
+            return Err(TriviaError::NotFound(format!("{} {}", kind, id)));
+        }
+
+        self.deleted.insert((kind, id), Utc::now());
+        Ok(())
+    }
+
+    fn purge_player(&mut self, player_id: i32) {
+        self.players.retain(|p| p.player_id != player_id);
+
+        // ON DELETE CASCADE from survival_runs, practice_cards and
+        // game_sessions, and from there session_answers
+        let removed: Vec<i32> = self
+            .sessions
+            .iter()
+            .filter(|s| s.player_id == player_id)
+            .map(|s| s.session_id)
+            .collect();
+        self.sessions.retain(|s| s.player_id != player_id);
+        self.session_answers
+            .retain(|a| !removed.contains(&a.session_id));
+        self.survival_runs.retain(|r| r.player_id != player_id);
+        self.practice_cards.retain(|c| c.player_id != player_id);
+    }
+
+    fn purge_game(&mut self, game_id: i32) {
+        self.games.retain(|g| g.game_id != game_id);
+
+        // ON DELETE CASCADE from game_tags, game_questions, survival_runs and
+        // game_sessions, and from there session_answers
+        self.game_tags.retain(|&(id, _)| id != game_id);
+        self.game_questions.retain(|gq| gq.game_id != game_id);
+        let removed: Vec<i32> = self
+            .sessions
+            .iter()
+            .filter(|s| s.game_id == game_id)
+            .map(|s| s.session_id)
+            .collect();
+        self.sessions.retain(|s| s.game_id != game_id);
+        self.session_answers
+            .retain(|a| !removed.contains(&a.session_id));
+        self.survival_runs.retain(|r| r.game_id != Some(game_id));
+    }
+
+    fn purge_question(&mut self, question_id: i32) {
+        self.questions.retain(|q| q.question_id != question_id);
+
+        // ON DELETE CASCADE from the answer tables, revisions, tags,
+        // game_questions, practice_cards and session_answers
+        self.delete_answer_rows(question_id);
+        self.question_revisions
+            .retain(|r| r.question_id != question_id);
+        self.question_tags.retain(|&(id, _)| id != question_id);
+        self.question_created.remove(&question_id);
+        self.game_questions
+            .retain(|gq| gq.question_id != question_id);
+        self.session_answers
+            .retain(|a| a.question_id != question_id);
+        self.practice_cards.retain(|c| c.question_id != question_id);
+    }
+
+    /// Returns the id of the tag called `name`, creating it if needed.
+    fn tag_id(&mut self, name: &str) -> i32 {
+        match self.tags.iter().position(|t| t == name) {
+            Some(index) => index as i32 + 1,
+            None => {
+                self.tags.push(name.to_string());
+                self.tags.len() as i32
+            }
+        }
+    }
+
+    /// Names of the tags linked to `id` in `links`, alphabetically.
+    fn tag_names(&self, links: &[(i32, i32)], id: i32) -> Vec<String> {
+        let mut names: Vec<String> = links
+            .iter()
+            .filter(|&&(linked, _)| linked == id)
+            .map(|&(_, tag_id)| self.tags[tag_id as usize - 1].clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Adds the choices, accepted answers, numeric target or items that
+    /// `answer` describes.
+    fn insert_answer_rows(&mut self, question_id: i32, answer: &AnswerSpec) {
+        match answer {
+            AnswerSpec::TrueFalse(_) => {}
+            AnswerSpec::MultipleChoice {
+                choices,
+                correct_choice,
+            } => {
+                for (i, choice) in choices.iter().enumerate() {
+                    let choice_id = next_id(&mut self.next_choice_id);
+                    self.question_choices.push(QuestionChoice {
+                        choice_id,
+                        question_id,
+                        choice_order: i as i32 + 1,
+                        choice_text: choice.trim().to_string(),
+                        is_correct: i == *correct_choice,
+                    });
+                }
+            }
+            AnswerSpec::FreeText {
+                accepted,
+                max_distance,
+            } => {
+                for answer in accepted {
+                    let accepted_answer_id = next_id(&mut self.next_accepted_answer_id);
+                    self.accepted_answers.push(AcceptedAnswer {
+                        accepted_answer_id,
+                        question_id,
+                        answer_text: answer.trim().to_string(),
+                        max_distance: *max_distance,
+                    });
+                }
+            }
+            AnswerSpec::Numeric {
+                target_value,
+                tolerance,
+                tolerance_percent,
+                unit,
+            } => {
+                self.numeric_answers.push(NumericAnswer {
+                    question_id,
+                    target_value: *target_value,
+                    tolerance: *tolerance,
+                    tolerance_percent: *tolerance_percent,
+                    unit: unit.clone(),
+                });
+            }
+            AnswerSpec::Ordering { items, .. } => {
+                for (i, item) in items.iter().enumerate() {
+                    let item_id = next_id(&mut self.next_item_id);
+                    self.ordering_items.push(OrderingItem {
+                        item_id,
+                        question_id,
+                        item_order: i as i32 + 1,
+                        item_text: item.trim().to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Removes whatever kind-specific answer rows a question has.
+    fn delete_answer_rows(&mut self, question_id: i32) {
+        self.question_choices
+            .retain(|c| c.question_id != question_id);
+        self.accepted_answers
+            .retain(|a| a.question_id != question_id);
+        self.numeric_answers
+            .retain(|a| a.question_id != question_id);
+        self.ordering_items.retain(|i| i.question_id != question_id);
+    }
+
+    fn renumber_game_questions(&mut self, game_id: i32) {
+        let mut entries: Vec<&mut GameQuestion> = self
+            .game_questions
+            .iter_mut()
+            .filter(|gq| gq.game_id == game_id)
+            .collect();
+        entries.sort_by_key(|gq| gq.question_order);
+
+        for (new_order, entry) in entries.into_iter().enumerate() {
+            entry.question_order = new_order as i32 + 1;
+        }
+    }
+}
+
+impl TriviaStore for MemoryStore {
+    // Nothing is persisted, so there is no schema to migrate.
+    async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        Ok(Vec::new())
+    }
+
+    async fn migrate_up(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn migrate_down(&self) -> Result<Option<i64>> {
+        Ok(None)
+    }
+
+    async fn get_games(&self) -> Result<Vec<Game>> {
+        let state = self.state();
+
+        Ok(state
+            .games
+            .iter()
+            .filter(|g| !state.is_deleted(TrashKind::Game, g.game_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn create_game(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        _admin_id: i32,
+    ) -> Result<i32> {
+        let mut state = self.state();
+
+        if state.games.iter().any(|g| g.title == title) {
+            return Err(TriviaError::DuplicateTitle(title.to_string()));
+        }
+
+        let game_id = next_id(&mut state.next_game_id);
+        state.games.push(Game {
+            game_id,
+            title: title.to_string(),
+            description: description.map(str::to_string),
+            play_order: PlayOrder::default(),
+            draw_count: None,
+        });
+
+        Ok(game_id)
+    }
+
+    async fn create_game_with_tags(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        tags: &[String],
+        _admin_id: i32,
+    ) -> Result<i32> {
+        let tags = normalize_tags(tags)?;
+        let mut state = self.state();
+
+        if state.games.iter().any(|g| g.title == title) {
+            return Err(TriviaError::DuplicateTitle(title.to_string()));
+        }
+
+        let game_id = next_id(&mut state.next_game_id);
+        state.games.push(Game {
+            game_id,
+            title: title.to_string(),
+            description: description.map(str::to_string),
+            play_order: PlayOrder::default(),
+            draw_count: None,
+        });
+        for tag in &tags {
+            let tag_id = state.tag_id(tag);
+            state.game_tags.push((game_id, tag_id));
+        }
+
+        Ok(game_id)
+    }
+
+    async fn get_game(&self, game_id: i32) -> Result<Game> {
+        let state = self.state();
+
+        state
+            .games
+            .iter()
+            .filter(|g| !state.is_deleted(TrashKind::Game, g.game_id))
+            .find(|g| g.game_id == game_id)
+            .cloned()
+            .ok_or_else(|| TriviaError::NotFound(format!("Game {}", game_id)))
+    }
+
+    async fn update_game(
+        &self,
+        game_id: i32,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<()> {
+        let mut state = self.state();
+
+        if state.is_deleted(TrashKind::Game, game_id) {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        if state
+            .games
+            .iter()
+            .any(|g| g.title == title && g.game_id != game_id)
+        {
+            return Err(TriviaError::DuplicateTitle(title.to_string()));
+        }
+
+        let game = state
+            .games
+            .iter_mut()
+            .find(|g| g.game_id == game_id)
+            .ok_or_else(|| TriviaError::NotFound(format!("Game {}", game_id)))?;
+        game.title = title.to_string();
+        game.description = description.map(str::to_string);
+
+        Ok(())
+    }
+
+    async fn update_game_with_tags(
+        &self,
+        game_id: i32,
+        title: &str,
+        description: Option<&str>,
+        tags: Option<&[String]>,
+    ) -> Result<()> {
+        let tags = tags.map(normalize_tags).transpose()?;
+        let mut state = self.state();
+
+        if state.is_deleted(TrashKind::Game, game_id) {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        if state
+            .games
+            .iter()
+            .any(|g| g.title == title && g.game_id != game_id)
+        {
+            return Err(TriviaError::DuplicateTitle(title.to_string()));
+        }
+
+        let game = state
+            .games
+            .iter_mut()
+            .find(|g| g.game_id == game_id)
+            .ok_or_else(|| TriviaError::NotFound(format!("Game {}", game_id)))?;
+        game.title = title.to_string();
+        game.description = description.map(str::to_string);
+
+        if let Some(tags) = tags {
+            state.game_tags.retain(|&(id, _)| id != game_id);
+            for tag in &tags {
+                let tag_id = state.tag_id(tag);
+                state.game_tags.push((game_id, tag_id));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn set_game_play_order(
+        &self,
+        game_id: i32,
+        play_order: PlayOrder,
+        draw_count: Option<i32>,
+    ) -> Result<()> {
+        validate_play_order(play_order, draw_count)?;
+        let mut state = self.state();
+
+        if state.is_deleted(TrashKind::Game, game_id) {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        let game = state
+            .games
+            .iter_mut()
+            .find(|g| g.game_id == game_id)
+            .ok_or_else(|| TriviaError::NotFound(format!("Game {}", game_id)))?;
+        game.play_order = play_order;
+        game.draw_count = draw_count;
+
+        Ok(())
+    }
+
+    async fn get_game_usage(&self, game_id: i32) -> Result<GameUsage> {
+        let state = self.state();
+
+        Ok(GameUsage {
+            question_count: state
+                .game_questions
+                .iter()
+                .filter(|gq| {
+                    gq.game_id == game_id && !state.is_deleted(TrashKind::Question, gq.question_id)
+                })
+                .count() as i64,
+            session_count: state
+                .sessions
+                .iter()
+                .filter(|s| s.game_id == game_id)
+                .count() as i64,
+        })
+    }
+
+    async fn delete_game(&self, game_id: i32) -> Result<()> {
+        self.state().soft_delete(TrashKind::Game, game_id)
+    }
+
+    async fn get_all_questions(&self) -> Result<Vec<Question>> {
+        let state = self.state();
+
+        Ok(state
+            .questions
+            .iter()
+            .filter(|q| !state.is_deleted(TrashKind::Question, q.question_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_question_usage(&self) -> Result<Vec<QuestionUsage>> {
+        let state = self.state();
+
+        let mut usage: Vec<QuestionUsage> = state
+            .questions
+            .iter()
+            .filter(|q| !state.is_deleted(TrashKind::Question, q.question_id))
+            .map(|q| QuestionUsage {
+                question_id: q.question_id,
+                question_text: q.question_text.clone(),
+                kind: q.kind,
+                difficulty: q.difficulty,
+                game_count: state
+                    .game_questions
+                    .iter()
+                    .filter(|gq| {
+                        gq.question_id == q.question_id
+                            && !state.is_deleted(TrashKind::Game, gq.game_id)
+                    })
+                    .count() as i64,
+                answer_count: state
+                    .session_answers
+                    .iter()
+                    .filter(|a| a.question_id == q.question_id)
+                    .count() as i64,
+            })
+            .collect();
+        usage.sort_by_key(|u| u.question_id);
+
+        Ok(usage)
+    }
+
+    async fn get_games_using_question(&self, question_id: i32) -> Result<Vec<Game>> {
+        let state = self.state();
+
+        let mut games: Vec<Game> = state
+            .games
+            .iter()
+            .filter(|g| {
+                !state.is_deleted(TrashKind::Game, g.game_id)
+                    && state
+                        .game_questions
+                        .iter()
+                        .any(|gq| gq.game_id == g.game_id && gq.question_id == question_id)
+            })
+            .cloned()
+            .collect();
+        games.sort_by(|a, b| a.title.cmp(&b.title));
+
+        Ok(games)
+    }
+
+    async fn create_question_with_details(
+        &self,
+        question: &NewQuestion,
+        admin_id: i32,
+    ) -> Result<i32> {
+        let tags = validate_new_question(question)?;
+        let mut state = self.state();
+
+        let question_id = next_id(&mut state.next_question_id);
+        state.questions.push(Question {
+            question_id,
+            question_text: question.text.trim().to_string(),
+            correct_answer: question.answer.correct_answer().into(),
+            kind: question.answer.kind(),
+            partial_credit: question.answer.partial_credit(),
+            difficulty: question.difficulty,
+            time_limit_secs: question.time_limit_secs,
+            explanation: question.explanation.clone(),
+            source: question.source.clone(),
+        });
+        state
+            .question_created
+            .insert(question_id, (admin_id, Utc::now()));
+
+        state.insert_answer_rows(question_id, &question.answer);
+        for tag in &tags {
+            let tag_id = state.tag_id(tag);
+            state.question_tags.push((question_id, tag_id));
+        }
+
+        Ok(question_id)
+    }
+
+    async fn create_question(&self, text: &str, answer: bool, admin_id: i32) -> Result<i32> {
+        let mut state = self.state();
+
+        let question_id = next_id(&mut state.next_question_id);
+        state.questions.push(Question {
+            question_id,
+            question_text: text.to_string(),
+            correct_answer: answer.into(),
+            kind: QuestionKind::TrueFalse,
+            partial_credit: false,
+            difficulty: Difficulty::default(),
+            time_limit_secs: None,
+            explanation: None,
+            source: None,
+        });
+        state
+            .question_created
+            .insert(question_id, (admin_id, Utc::now()));
+
+        Ok(question_id)
+    }
+
+    async fn create_multiple_choice_question(
+        &self,
+        text: &str,
+        choices: &[String],
+        correct_choice: usize,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_choices(choices, correct_choice)?;
+        let mut state = self.state();
+
+        let question_id = next_id(&mut state.next_question_id);
+        state.questions.push(Question {
+            question_id,
+            question_text: text.to_string(),
+            correct_answer: Answer::False,
+            kind: QuestionKind::MultipleChoice,
+            partial_credit: false,
+            difficulty: Difficulty::default(),
+            time_limit_secs: None,
+            explanation: None,
+            source: None,
+        });
+        state
+            .question_created
+            .insert(question_id, (admin_id, Utc::now()));
+
+        state.insert_answer_rows(
+            question_id,
+            &AnswerSpec::MultipleChoice {
+                choices: choices.to_vec(),
+                correct_choice,
+            },
+        );
+
+        Ok(question_id)
+    }
+
+    async fn get_question_choices(&self, question_id: i32) -> Result<Vec<QuestionChoice>> {
+        let mut choices: Vec<QuestionChoice> = self
+            .state()
+            .question_choices
+            .iter()
+            .filter(|c| c.question_id == question_id)
+            .cloned()
+            .collect();
+        choices.sort_by_key(|c| c.choice_order);
+
+        Ok(choices)
+    }
+
+    async fn create_free_text_question(
+        &self,
+        text: &str,
+        accepted: &[String],
+        max_distance: i32,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_accepted_answers(accepted, max_distance)?;
+        let mut state = self.state();
+
+        let question_id = next_id(&mut state.next_question_id);
+        state.questions.push(Question {
+            question_id,
+            question_text: text.to_string(),
+            correct_answer: Answer::False,
+            kind: QuestionKind::FreeText,
+            partial_credit: false,
+            difficulty: Difficulty::default(),
+            time_limit_secs: None,
+            explanation: None,
+            source: None,
+        });
+        state
+            .question_created
+            .insert(question_id, (admin_id, Utc::now()));
+
+        state.insert_answer_rows(
+            question_id,
+            &AnswerSpec::FreeText {
+                accepted: accepted.to_vec(),
+                max_distance,
+            },
+        );
+
+        Ok(question_id)
+    }
+
+    async fn get_accepted_answers(&self, question_id: i32) -> Result<Vec<AcceptedAnswer>> {
+        Ok(self
+            .state()
+            .accepted_answers
+            .iter()
+            .filter(|a| a.question_id == question_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn create_numeric_question(
+        &self,
+        text: &str,
+        target_value: f64,
+        tolerance: f64,
+        tolerance_percent: bool,
+        unit: Option<&str>,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_numeric_answer(target_value, tolerance, unit)?;
+        let mut state = self.state();
+
+        let question_id = next_id(&mut state.next_question_id);
+        state.questions.push(Question {
+            question_id,
+            question_text: text.to_string(),
+            correct_answer: Answer::False,
+            kind: QuestionKind::Numeric,
+            partial_credit: false,
+            difficulty: Difficulty::default(),
+            time_limit_secs: None,
+            explanation: None,
+            source: None,
+        });
+        state
+            .question_created
+            .insert(question_id, (admin_id, Utc::now()));
+        state.insert_answer_rows(
+            question_id,
+            &AnswerSpec::Numeric {
+                target_value,
+                tolerance,
+                tolerance_percent,
+                unit: unit.map(str::to_string),
+            },
+        );
+
+        Ok(question_id)
+    }
+
+    async fn get_numeric_answer(&self, question_id: i32) -> Result<NumericAnswer> {
+        self.state()
+            .numeric_answers
+            .iter()
+            .find(|a| a.question_id == question_id)
+            .cloned()
+            .ok_or_else(|| TriviaError::NotFound(format!("Answer for question {}", question_id)))
+    }
+
+    async fn create_ordering_question(
+        &self,
+        text: &str,
+        items: &[String],
+        partial_credit: bool,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_ordering_items(items)?;
+        let mut state = self.state();
+
+        let question_id = next_id(&mut state.next_question_id);
+        state.questions.push(Question {
+            question_id,
+            question_text: text.to_string(),
+            correct_answer: Answer::False,
+            kind: QuestionKind::Ordering,
+            partial_credit,
+            difficulty: Difficulty::default(),
+            time_limit_secs: None,
+            explanation: None,
+            source: None,
+        });
+        state
+            .question_created
+            .insert(question_id, (admin_id, Utc::now()));
+
+        state.insert_answer_rows(
+            question_id,
+            &AnswerSpec::Ordering {
+                items: items.to_vec(),
+                partial_credit,
+            },
+        );
+
+        Ok(question_id)
+    }
+
+    async fn get_ordering_items(&self, question_id: i32) -> Result<Vec<OrderingItem>> {
+        let mut items: Vec<OrderingItem> = self
+            .state()
+            .ordering_items
+            .iter()
+            .filter(|i| i.question_id == question_id)
+            .cloned()
+            .collect();
+        items.sort_by_key(|i| i.item_order);
+
+        Ok(items)
+    }
+
+    async fn set_question_explanation(
+        &self,
+        question_id: i32,
+        explanation: Option<&str>,
+        source: Option<&str>,
+    ) -> Result<()> {
+        let mut state = self.state();
+
+        if state.is_deleted(TrashKind::Question, question_id) {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        let question = state
+            .questions
+            .iter_mut()
+            .find(|q| q.question_id == question_id)
+            .ok_or_else(|| TriviaError::NotFound(format!("Question {}", question_id)))?;
+        question.explanation = explanation.map(str::to_string);
+        question.source = source.map(str::to_string);
+
+        Ok(())
+    }
+
+    async fn set_question_difficulty(
+        &self,
+        question_id: i32,
+        difficulty: Difficulty,
+    ) -> Result<()> {
+        let mut state = self.state();
+
+        if state.is_deleted(TrashKind::Question, question_id) {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        let question = state
+            .questions
+            .iter_mut()
+            .find(|q| q.question_id == question_id)
+            .ok_or_else(|| TriviaError::NotFound(format!("Question {}", question_id)))?;
+        question.difficulty = difficulty;
+
+        Ok(())
+    }
+
+    async fn set_question_time_limit(&self, question_id: i32, seconds: Option<i32>) -> Result<()> {
+        validate_time_limit(seconds)?;
+        let mut state = self.state();
+
+        if state.is_deleted(TrashKind::Question, question_id) {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        let question = state
+            .questions
+            .iter_mut()
+            .find(|q| q.question_id == question_id)
+            .ok_or_else(|| TriviaError::NotFound(format!("Question {}", question_id)))?;
+        question.time_limit_secs = seconds;
+
+        Ok(())
+    }
+
+    async fn delete_question(&self, question_id: i32) -> Result<()> {
+        self.state().soft_delete(TrashKind::Question, question_id)
+    }
+
+    async fn get_question(&self, question_id: i32) -> Result<Question> {
+        let state = self.state();
+
+        state
+            .questions
+            .iter()
+            .filter(|q| !state.is_deleted(TrashKind::Question, q.question_id))
+            .find(|q| q.question_id == question_id)
+            .cloned()
+            .ok_or_else(|| TriviaError::NotFound(format!("Question {}", question_id)))
+    }
+
+    async fn update_question(
+        &self,
+        question_id: i32,
+        text: &str,
+        answer: &AnswerSpec,
+        explanation: Option<&str>,
+        source: Option<&str>,
+        admin_id: i32,
+    ) -> Result<()> {
+        let current = self.get_question(question_id).await?;
+        let current_answer = self.get_answer_spec(&current).await?;
+        if !check_edit(&current, &current_answer, text, answer, explanation, source)? {
+            return Ok(());
+        }
+        let previous_answer = current_answer.to_json()?;
+
+        let mut state = self.state();
+
+        let revision_id = next_id(&mut state.next_revision_id);
+        state.question_revisions.push(QuestionRevision {
+            revision_id,
+            question_id,
+            question_text: current.question_text,
+            answer: previous_answer,
+            explanation: current.explanation,
+            source: current.source,
+            edited_by: Some(admin_id),
+            editor: None,
+            edited_at: Some(Utc::now()),
+        });
+
+        let question = state
+            .questions
+            .iter_mut()
+            .find(|q| q.question_id == question_id)
+            .ok_or_else(|| TriviaError::NotFound(format!("Question {}", question_id)))?;
+        question.question_text = text.to_string();
+        question.correct_answer = answer.correct_answer().into();
+        question.partial_credit = answer.partial_credit();
+        question.explanation = explanation.map(str::to_string);
+        question.source = source.map(str::to_string);
+
+        state.delete_answer_rows(question_id);
+        state.insert_answer_rows(question_id, answer);
+
+        Ok(())
+    }
+
+    async fn get_question_revisions(&self, question_id: i32) -> Result<Vec<QuestionRevision>> {
+        let state = self.state();
+
+        let mut revisions: Vec<QuestionRevision> = state
+            .question_revisions
+            .iter()
+            .filter(|r| r.question_id == question_id)
+            .map(|r| QuestionRevision {
+                // LEFT JOIN admins
+                editor: state
+                    .admins
+                    .iter()
+                    .find(|a| Some(a.admin_id) == r.edited_by)
+                    .map(|a| a.username.clone()),
+                ..r.clone()
+            })
+            .collect();
+        revisions.sort_by_key(|r| Reverse(r.revision_id));
+
+        Ok(revisions)
+    }
+
+    async fn get_game_questions(&self, game_id: i32) -> Result<Vec<GameQuestionFull>> {
+        let state = self.state();
+
+        let mut questions: Vec<GameQuestionFull> = state
+            .game_questions
+            .iter()
+            .filter(|gq| {
+                gq.game_id == game_id && !state.is_deleted(TrashKind::Question, gq.question_id)
+            })
+            .filter_map(|gq| {
+                state
+                    .questions
+                    .iter()
+                    .find(|q| q.question_id == gq.question_id)
+                    .map(|q| GameQuestionFull {
+                        question_id: q.question_id,
+                        question_text: q.question_text.clone(),
+                        correct_answer: q.correct_answer,
+                        kind: q.kind,
+                        partial_credit: q.partial_credit,
+                        difficulty: q.difficulty,
+                        time_limit_secs: q.time_limit_secs,
+                        explanation: q.explanation.clone(),
+                        source: q.source.clone(),
+                        question_order: gq.question_order,
+                    })
+            })
+            .collect();
+        questions.sort_by_key(|q| q.question_order);
+
+        Ok(questions)
+    }
+
+    async fn search_questions(&self, search: &QuestionSearch, limit: i32) -> Result<Vec<Question>> {
+        let terms = search_terms(search)?;
+        let state = self.state();
+        let tag = search.tag.as_deref().map(normalize_tag);
+        let tag_id = tag
+            .as_ref()
+            .and_then(|tag| state.tags.iter().position(|t| t == tag))
+            .map(|index| index as i32 + 1);
+
+        let matches = |question: &Question| {
+            if state.is_deleted(TrashKind::Question, question.question_id) {
+                return false;
+            }
+            if let Some(terms) = &terms {
+                let text = format!(
+                    "{} {}",
+                    question.question_text,
+                    question.explanation.as_deref().unwrap_or("")
+                )
+                .to_lowercase();
+                let words: Vec<&str> = text.split(|c: char| !c.is_alphanumeric()).collect();
+                if !terms
+                    .iter()
+                    .all(|term| words.iter().any(|word| word.starts_with(term.as_str())))
+                {
+                    return false;
+                }
+            }
+            if tag.is_some()
+                && !tag_id.is_some_and(|t| state.question_tags.contains(&(question.question_id, t)))
+            {
+                return false;
+            }
+            if search.kind.is_some_and(|kind| kind != question.kind) {
+                return false;
+            }
+
+            let created = state.question_created.get(&question.question_id);
+            if let Some(author) = &search.author {
+                let created_by = created.and_then(|&(admin_id, _)| {
+                    state.admins.iter().find(|a| a.admin_id == admin_id)
+                });
+                if created_by.is_none_or(|a| a.username != *author) {
+                    return false;
+                }
+            }
+            let created_on = created.map(|(_, at)| at.date_naive());
+            if search
+                .created_from
+                .is_some_and(|from| created_on.is_none_or(|on| on < from))
+                || search
+                    .created_to
+                    .is_some_and(|to| created_on.is_none_or(|on| on > to))
+            {
+                return false;
+            }
+            true
+        };
+
+        Ok(state
+            .questions
+            .iter()
+            .filter(|q| matches(q))
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn add_question_to_game(&self, game_id: i32, question_id: i32, order: i32) -> Result<()> {
+        let mut state = self.state();
+
+        if !state.games.iter().any(|g| g.game_id == game_id)
+            || state.is_deleted(TrashKind::Game, game_id)
+        {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+        if !state.questions.iter().any(|q| q.question_id == question_id)
+            || state.is_deleted(TrashKind::Question, question_id)
+        {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+        if state
+            .game_questions
+            .iter()
+            .any(|gq| gq.game_id == game_id && gq.question_id == question_id)
+        {
+            return Err(TriviaError::InvalidInput(format!(
+                "Question {} is already in this game",
+                question_id
+            )));
+        }
+
+        state.game_questions.push(GameQuestion {
+            game_id,
+            question_id,
+            question_order: order,
+        });
+
+        Ok(())
+    }
+
+    async fn delete_game_question(&self, game_id: i32, question_id: i32) -> Result<()> {
+        let mut state = self.state();
+
+        let before = state.game_questions.len();
+        state
+            .game_questions
+            .retain(|gq| !(gq.game_id == game_id && gq.question_id == question_id));
+        if state.game_questions.len() == before {
+            return Err(TriviaError::NotFound(format!(
+                "Question {} in game {}",
+                question_id, game_id
+            )));
+        }
+
+        state.renumber_game_questions(game_id);
+
+        Ok(())
+    }
+
+    async fn update_question_order(
+        &self,
+        game_id: i32,
+        question_id: i32,
+        order: i32,
+    ) -> Result<()> {
+        let mut state = self.state();
+
+        if state.is_deleted(TrashKind::Game, game_id)
+            || state.is_deleted(TrashKind::Question, question_id)
+        {
+            return Err(TriviaError::NotFound(format!(
+                "Question {} in game {}",
+                question_id, game_id
+            )));
+        }
+
+        match state
+            .game_questions
+            .iter_mut()
+            .find(|gq| gq.game_id == game_id && gq.question_id == question_id)
+        {
+            Some(entry) => {
+                entry.question_order = order;
+                Ok(())
+            }
+            None => Err(TriviaError::NotFound(format!(
+                "Question {} in game {}",
+                question_id, game_id
+            ))),
+        }
+    }
+
+    async fn reorder_game_questions(&self, game_id: i32, question_ids: &[i32]) -> Result<()> {
+        check_question_order(&self.get_game_questions(game_id).await?, question_ids)?;
+        let mut state = self.state();
+
+        for entry in state
+            .game_questions
+            .iter_mut()
+            .filter(|gq| gq.game_id == game_id)
+        {
+            if let Some(index) = question_ids.iter().position(|&id| id == entry.question_id) {
+                entry.question_order = index as i32 + 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_max_question_order(&self, game_id: i32) -> Result<i32> {
+        Ok(self
+            .state()
+            .game_questions
+            .iter()
+            .filter(|gq| gq.game_id == game_id)
+            .map(|gq| gq.question_order)
+            .max()
+            .unwrap_or(0))
+    }
+
+    async fn login_player(&self, username: &str, password: &str) -> Result<Player> {
+        let mut state = self.state();
+        let State {
+            players, deleted, ..
+        } = &mut *state;
+
+        let player = players
+            .iter_mut()
+            .filter(|p| !deleted.contains_key(&(TrashKind::Player, p.player_id)))
+            .find(|p| p.username == username)
+            .ok_or(TriviaError::Unauthorized)?;
+
+        match verify_password(password, &player.password_hash) {
+            PasswordCheck::Valid => Ok(player.clone()),
+            PasswordCheck::ValidLegacy => {
+                player.password_hash = hash_password(password)?;
+                Ok(player.clone())
+            }
+            PasswordCheck::Invalid => Err(TriviaError::Unauthorized),
+        }
+    }
+
+    async fn get_all_players(&self) -> Result<Vec<Player>> {
+        let state = self.state();
+
+        Ok(state
+            .players
+            .iter()
+            .filter(|p| !state.is_deleted(TrashKind::Player, p.player_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn create_new_player(&self, username: &str, password: &str) -> Result<()> {
+        let password_hash = hash_password(password)?;
+        let mut state = self.state();
+
+        if state.players.iter().any(|p| p.username == username) {
+            return Err(TriviaError::DuplicateUsername(username.to_string()));
+        }
+
+        let player_id = next_id(&mut state.next_player_id);
+        state.players.push(Player {
+            player_id,
+            username: username.to_string(),
+            password_hash,
+            created_at: Some(Utc::now()),
+        });
+
+        Ok(())
+    }
+
+    async fn delete_player(&self, player_id: i32) -> Result<()> {
+        self.state().soft_delete(TrashKind::Player, player_id)
+    }
+
+    async fn get_trash(&self) -> Result<Vec<TrashItem>> {
+        let state = self.state();
+
+        let mut items: Vec<TrashItem> = state
+            .deleted
+            .iter()
+            .filter_map(|(&(kind, id), &deleted_at)| {
+                let name = match kind {
+                    TrashKind::Player => state
+                        .players
+                        .iter()
+                        .find(|p| p.player_id == id)
+                        .map(|p| p.username.clone()),
+                    TrashKind::Game => state
+                        .games
+                        .iter()
+                        .find(|g| g.game_id == id)
+                        .map(|g| g.title.clone()),
+                    TrashKind::Question => state
+                        .questions
+                        .iter()
+                        .find(|q| q.question_id == id)
+                        .map(|q| q.question_text.clone()),
+                }?;
+                Some(TrashItem {
+                    kind,
+                    id,
+                    name,
+                    deleted_at: Some(deleted_at),
+                })
+            })
+            .collect();
+        items.sort_by_key(|i| Reverse(i.deleted_at));
+
+        Ok(items)
+    }
+
+    async fn restore_item(&self, kind: TrashKind, id: i32) -> Result<()> {
+        self.state()
+            .deleted
+            .remove(&(kind, id))
+            .map(|_| ())
+            .ok_or_else(|| TriviaError::NotFound(format!("{} {} in the trash", kind, id)))
+    }
+
+    async fn purge_item(&self, kind: TrashKind, id: i32) -> Result<()> {
+        let mut state = self.state();
+
+        if state.deleted.remove(&(kind, id)).is_none() {
+            return Err(TriviaError::NotFound(format!(
+                "{} {} in the trash",
+                kind, id
+            )));
+        }
+        match kind {
+            TrashKind::Player => state.purge_player(id),
+            TrashKind::Game => state.purge_game(id),
+            TrashKind::Question => state.purge_question(id),
+        }
+
+        Ok(())
+    }
+
+    async fn get_tags(&self) -> Result<Vec<Tag>> {
+        let state = self.state();
+
+        let mut tags: Vec<Tag> = state
+            .tags
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let tag_id = index as i32 + 1;
+                Tag {
+                    tag_id,
+                    name: name.clone(),
+                    question_count: state
+                        .question_tags
+                        .iter()
+                        .filter(|&&(id, t)| {
+                            t == tag_id && !state.is_deleted(TrashKind::Question, id)
+                        })
+                        .count() as i64,
+                    game_count: state
+                        .game_tags
+                        .iter()
+                        .filter(|&&(id, t)| t == tag_id && !state.is_deleted(TrashKind::Game, id))
+                        .count() as i64,
+                }
+            })
+            .collect();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(tags)
+    }
+
+    async fn get_question_tags(&self, question_id: i32) -> Result<Vec<String>> {
+        let state = self.state();
+        Ok(state.tag_names(&state.question_tags, question_id))
+    }
+
+    async fn set_question_tags(&self, question_id: i32, tags: &[String]) -> Result<()> {
+        self.get_question(question_id).await?;
+        let tags = normalize_tags(tags)?;
+        let mut state = self.state();
+
+        state.question_tags.retain(|&(id, _)| id != question_id);
+        for tag in &tags {
+            let tag_id = state.tag_id(tag);
+            state.question_tags.push((question_id, tag_id));
+        }
+
+        Ok(())
+    }
+
+    async fn get_game_tags(&self, game_id: i32) -> Result<Vec<String>> {
+        let state = self.state();
+        Ok(state.tag_names(&state.game_tags, game_id))
+    }
+
+    async fn set_game_tags(&self, game_id: i32, tags: &[String]) -> Result<()> {
+        self.get_game(game_id).await?;
+        let tags = normalize_tags(tags)?;
+        let mut state = self.state();
+
+        state.game_tags.retain(|&(id, _)| id != game_id);
+        for tag in &tags {
+            let tag_id = state.tag_id(tag);
+            state.game_tags.push((game_id, tag_id));
+        }
+
+        Ok(())
+    }
+
+    async fn get_questions_by_tag(&self, tag: &str) -> Result<Vec<Question>> {
+        let state = self.state();
+        let tag = normalize_tag(tag);
+        let Some(index) = state.tags.iter().position(|t| *t == tag) else {
+            return Ok(Vec::new());
+        };
+        let tag_id = index as i32 + 1;
+
+        Ok(state
+            .questions
+            .iter()
+            .filter(|q| {
+                !state.is_deleted(TrashKind::Question, q.question_id)
+                    && state.question_tags.contains(&(q.question_id, tag_id))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn get_games_by_tag(&self, tag: &str) -> Result<Vec<Game>> {
+        let state = self.state();
+        let tag = normalize_tag(tag);
+        let Some(index) = state.tags.iter().position(|t| *t == tag) else {
+            return Ok(Vec::new());
+        };
+        let tag_id = index as i32 + 1;
+
+        Ok(state
+            .games
+            .iter()
+            .filter(|g| {
+                !state.is_deleted(TrashKind::Game, g.game_id)
+                    && state.game_tags.contains(&(g.game_id, tag_id))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn login_admin(&self, username: &str, password: &str) -> Result<Admin> {
+        let mut state = self.state();
+
+        let admin = state
+            .admins
+            .iter_mut()
+            .find(|a| a.username == username)
+            .ok_or(TriviaError::Unauthorized)?;
+
+        match verify_password(password, &admin.password_hash) {
+            PasswordCheck::Valid => Ok(admin.clone()),
+            PasswordCheck::ValidLegacy => {
+                admin.password_hash = hash_password(password)?;
+                Ok(admin.clone())
+            }
+            PasswordCheck::Invalid => Err(TriviaError::Unauthorized),
+        }
+    }
+
+    async fn start_session(
+        &self,
+        player_id: i32,
+        game_id: i32,
+        mode: GameMode,
+        seed: Option<i64>,
+    ) -> Result<i32> {
+        let mut state = self.state();
+
+        if !state.players.iter().any(|p| p.player_id == player_id) {
+            return Err(TriviaError::NotFound(format!("Player {}", player_id)));
+        }
+        let (play_order, draw_count) = match state.games.iter().find(|g| g.game_id == game_id) {
+            Some(game) if !state.is_deleted(TrashKind::Game, game_id) => {
+                (game.play_order, game.draw_count)
+            }
+            _ => return Err(TriviaError::NotFound(format!("Game {}", game_id))),
+        };
+
+        let session_id = next_id(&mut state.next_session_id);
+        state.sessions.push(GameSession {
+            session_id,
+            player_id,
+            game_id,
+            mode,
+            seed,
+            play_order,
+            draw_count,
+            started_at: Utc::now(),
+            ended_at: None,
+            correct_answers: 0,
+            total_questions: 0,
+            score: 0.0,
+            max_score: 0.0,
+            bonus: 0.0,
+        });
+
+        Ok(session_id)
+    }
+
+    async fn record_answer(
+        &self,
+        session_id: i32,
+        question_id: i32,
+        submitted_answer: &str,
+        is_correct: bool,
+        score: f64,
+        response_time_ms: i32,
+    ) -> Result<()> {
+        let mut state = self.state();
+
+        if !state.sessions.iter().any(|s| s.session_id == session_id) {
+            return Err(TriviaError::NotFound(format!("Session {}", session_id)));
+        }
+
+        let answer_id = next_id(&mut state.next_answer_id);
+        state.session_answers.push(SessionAnswer {
+            answer_id,
+            session_id,
+            question_id,
+            submitted_answer: submitted_answer.to_string(),
+            is_correct,
+            score,
+            response_time_ms,
+            answered_at: Some(Utc::now()),
+        });
+
+        Ok(())
+    }
+
+    async fn finish_session(
+        &self,
+        session_id: i32,
+        correct_answers: i32,
+        total_questions: i32,
+        score: f64,
+        max_score: f64,
+        bonus: f64,
+    ) -> Result<()> {
+        let mut state = self.state();
+
+        let session = state
+            .sessions
+            .iter_mut()
+            .find(|s| s.session_id == session_id)
+            .ok_or_else(|| TriviaError::NotFound(format!("Session {}", session_id)))?;
+
+        session.ended_at = Some(Utc::now());
+        session.correct_answers = correct_answers;
+        session.total_questions = total_questions;
+        session.score = score;
+        session.max_score = max_score;
+        session.bonus = bonus;
+
+        Ok(())
+    }
+
+    async fn get_session(&self, session_id: i32) -> Result<GameSession> {
+        self.state()
+            .sessions
+            .iter()
+            .find(|s| s.session_id == session_id)
+            .cloned()
+            .ok_or_else(|| TriviaError::NotFound(format!("Session {}", session_id)))
+    }
+
+    async fn get_player_sessions(&self, player_id: i32) -> Result<Vec<GameSession>> {
+        let mut sessions: Vec<GameSession> = self
+            .state()
+            .sessions
+            .iter()
+            .filter(|s| s.player_id == player_id)
+            .cloned()
+            .collect();
+        sessions.sort_by_key(|s| Reverse((s.started_at, s.session_id)));
+
+        Ok(sessions)
+    }
+
+    async fn get_session_answers(&self, session_id: i32) -> Result<Vec<SessionAnswer>> {
+        Ok(self
+            .state()
+            .session_answers
+            .iter()
+            .filter(|a| a.session_id == session_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_game_leaderboard(
+        &self,
+        game_id: i32,
+        mode: GameMode,
+        basis: LeaderboardBasis,
+        limit: i32,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        Ok(self.state().leaderboard(Some(game_id), mode, basis, limit))
+    }
+
+    async fn get_global_leaderboard(
+        &self,
+        mode: GameMode,
+        basis: LeaderboardBasis,
+        limit: i32,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        Ok(self.state().leaderboard(None, mode, basis, limit))
+    }
+
+    async fn record_survival_run(
+        &self,
+        player_id: i32,
+        game_id: Option<i32>,
+        lives: i32,
+        questions_answered: i32,
+        correct_answers: i32,
+        score: f64,
+    ) -> Result<i32> {
+        let mut state = self.state();
+
+        if !state.players.iter().any(|p| p.player_id == player_id) {
+            return Err(TriviaError::NotFound(format!("Player {}", player_id)));
+        }
+        if let Some(game_id) = game_id {
+            if !state.games.iter().any(|g| g.game_id == game_id) {
+                return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+            }
+        }
+
+        let run_id = next_id(&mut state.next_run_id);
+        state.survival_runs.push(SurvivalRun {
+            run_id,
+            player_id,
+            game_id,
+            lives,
+            questions_answered,
+            correct_answers,
+            score,
+            played_at: Utc::now(),
+        });
+
+        Ok(run_id)
+    }
+
+    async fn get_survival_high_scores(
+        &self,
+        game_id: Option<i32>,
+        lives: i32,
+        limit: i32,
+    ) -> Result<Vec<SurvivalScore>> {
+        let state = self.state();
+
+        // Most correct answers, then most points, then whoever got there first.
+        let rank_cmp = |a: &&SurvivalRun, b: &&SurvivalRun| {
+            b.correct_answers
+                .cmp(&a.correct_answers)
+                .then(b.score.total_cmp(&a.score))
+                .then(a.played_at.cmp(&b.played_at))
+        };
+
+        let mut best: HashMap<i32, &SurvivalRun> = HashMap::new();
+        for run in state.survival_runs.iter().filter(|r| {
+            r.game_id == game_id
+                && r.lives == lives
+                && !state.is_deleted(TrashKind::Player, r.player_id)
+                && !r
+                    .game_id
+                    .is_some_and(|id| state.is_deleted(TrashKind::Game, id))
+        }) {
+            best.entry(run.player_id)
+                .and_modify(|current| {
+                    if rank_cmp(&run, current).is_lt() {
+                        *current = run;
+                    }
+                })
+                .or_insert(run);
+        }
+
+        let mut runs: Vec<&SurvivalRun> = best.into_values().collect();
+        runs.sort_by(rank_cmp);
+
+        Ok(runs
+            .into_iter()
+            .filter_map(|run| {
+                state
+                    .players
+                    .iter()
+                    .find(|p| p.player_id == run.player_id)
+                    .map(|p| (p, run))
+            })
+            .take(limit.max(0) as usize)
+            .enumerate()
+            .map(|(i, (player, run))| SurvivalScore {
+                rank: i as i64 + 1,
+                player_id: player.player_id,
+                username: player.username.clone(),
+                correct_answers: run.correct_answers,
+                questions_answered: run.questions_answered,
+                score: run.score,
+                played_at: run.played_at,
+            })
+            .collect())
+    }
+
+    async fn get_practice_questions(
+        &self,
+        player_id: i32,
+        now: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<GameQuestionFull>> {
+        let state = self.state();
+
+        let misses: Vec<&SessionAnswer> = state
+            .session_answers
+            .iter()
+            .filter(|a| {
+                !a.is_correct
+                    && state
+                        .sessions
+                        .iter()
+                        .any(|s| s.session_id == a.session_id && s.player_id == player_id)
+            })
+            .collect();
+
+        // Due cards wait from their due date, cards missed in a game since
+        // their last practice from that miss, and missed questions without a
+        // card from the first time they were missed.
+        let mut queue: Vec<(DateTime<Utc>, i32)> = state
+            .practice_cards
+            .iter()
+            .filter(|c| c.player_id == player_id)
+            .filter_map(|c| {
+                if c.due_at <= now {
+                    return Some((c.due_at, c.question_id));
+                }
+                misses
+                    .iter()
+                    .filter(|a| a.question_id == c.question_id)
+                    .filter_map(|a| a.answered_at)
+                    .filter(|&answered_at| answered_at > c.reviewed_at)
+                    .min()
+                    .map(|missed_at| (missed_at, c.question_id))
+            })
+            .collect();
+        let mut missed: HashMap<i32, DateTime<Utc>> = HashMap::new();
+        for answer in misses.iter().filter(|a| {
+            !state
+                .practice_cards
+                .iter()
+                .any(|c| c.player_id == player_id && c.question_id == a.question_id)
+        }) {
+            let answered_at = answer.answered_at.unwrap_or(now);
+            missed
+                .entry(answer.question_id)
+                .and_modify(|first| *first = (*first).min(answered_at))
+                .or_insert(answered_at);
+        }
+        queue.extend(
+            missed
+                .into_iter()
+                .map(|(question_id, first)| (first, question_id)),
+        );
+        queue.sort();
+
+        Ok(queue
+            .into_iter()
+            .filter(|&(_, question_id)| !state.is_deleted(TrashKind::Question, question_id))
+            .filter_map(|(_, question_id)| {
+                state
+                    .questions
+                    .iter()
+                    .find(|q| q.question_id == question_id)
+            })
+            .take(limit.max(0) as usize)
+            .enumerate()
+            .map(|(i, q)| GameQuestionFull {
+                question_id: q.question_id,
+                question_text: q.question_text.clone(),
+                correct_answer: q.correct_answer,
+                kind: q.kind,
+                partial_credit: q.partial_credit,
+                difficulty: q.difficulty,
+                time_limit_secs: q.time_limit_secs,
+                explanation: q.explanation.clone(),
+                source: q.source.clone(),
+                question_order: i as i32 + 1,
+            })
+            .collect())
+    }
+
+    async fn get_practice_card(
+        &self,
+        player_id: i32,
+        question_id: i32,
+    ) -> Result<Option<PracticeCard>> {
+        Ok(self
+            .state()
+            .practice_cards
+            .iter()
+            .find(|c| c.player_id == player_id && c.question_id == question_id)
+            .cloned())
+    }
+
+    async fn save_practice_card(&self, card: &PracticeCard) -> Result<()> {
+        let mut state = self.state();
+
+        if !state.players.iter().any(|p| p.player_id == card.player_id) {
+            return Err(TriviaError::NotFound(format!("Player {}", card.player_id)));
+        }
+        if !state
+            .questions
+            .iter()
+            .any(|q| q.question_id == card.question_id)
+        {
+            return Err(TriviaError::NotFound(format!(
+                "Question {}",
+                card.question_id
+            )));
+        }
+
+        state
+            .practice_cards
+            .retain(|c| !(c.player_id == card.player_id && c.question_id == card.question_id));
+        state.practice_cards.push(card.clone());
+
+        Ok(())
+    }
+
+    async fn get_next_practice_due(&self, player_id: i32) -> Result<Option<DateTime<Utc>>> {
+        let state = self.state();
+
+        Ok(state
+            .practice_cards
+            .iter()
+            .filter(|c| {
+                c.player_id == player_id && !state.is_deleted(TrashKind::Question, c.question_id)
+            })
+            .map(|c| c.due_at)
+            .min())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn store_with_admin() -> (MemoryStore, i32) {
+        let store = MemoryStore::new();
+        let admin_id = store.create_admin("admin", "secret").unwrap();
+        (store, admin_id)
+    }
+
+    #[tokio::test]
+    async fn admin_login_checks_the_password() {
+        let (store, admin_id) = store_with_admin();
+
+        let admin = store.login_admin("admin", "secret").await.unwrap();
+        assert_eq!(admin.admin_id, admin_id);
+        assert!(matches!(
+            store.login_admin("admin", "wrong").await,
+            Err(TriviaError::Unauthorized)
+        ));
+        assert!(matches!(
+            store.login_admin("nobody", "secret").await,
+            Err(TriviaError::Unauthorized)
+        ));
+        assert!(matches!(
+            store.create_admin("admin", "other"),
+            Err(TriviaError::DuplicateUsername(_))
+        ));
+    }
+
+    #[test]
+    fn from_url_adds_the_admin_in_the_url() {
+        let store = MemoryStore::from_url("memory://admin:secret@").unwrap();
+        assert_eq!(store.state().admins[0].username, "admin");
+
+        assert!(MemoryStore::from_url("memory:")
+            .unwrap()
+            .state()
+            .admins
+            .is_empty());
+        assert!(MemoryStore::from_url("memory://admin@").is_err());
+        assert!(MemoryStore::from_url("sqlite://trivia.db").is_err());
+    }
+
+    #[tokio::test]
+    async fn trashed_players_cannot_log_in_until_restored() {
+        let store = MemoryStore::new();
+        store.create_new_player("player", "secret").await.unwrap();
+        assert!(matches!(
+            store.create_new_player("player", "other").await,
+            Err(TriviaError::DuplicateUsername(_))
+        ));
+
+        let player = store.login_player("player", "secret").await.unwrap();
+        assert!(matches!(
+            store.login_player("player", "wrong").await,
+            Err(TriviaError::Unauthorized)
+        ));
+
+        store.delete_player(player.player_id).await.unwrap();
+        assert!(store.get_all_players().await.unwrap().is_empty());
+        assert!(matches!(
+            store.login_player("player", "secret").await,
+            Err(TriviaError::Unauthorized)
+        ));
+
+        store
+            .restore_item(TrashKind::Player, player.player_id)
+            .await
+            .unwrap();
+        assert!(store.login_player("player", "secret").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn game_titles_are_unique() {
+        let (store, admin_id) = store_with_admin();
+
+        let game_id = store.create_game("Science", None, admin_id).await.unwrap();
+        assert!(matches!(
+            store.create_game("Science", None, admin_id).await,
+            Err(TriviaError::DuplicateTitle(_))
+        ));
+
+        store
+            .update_game(game_id, "Physics", Some("Forces"))
+            .await
+            .unwrap();
+        let game = store.get_game(game_id).await.unwrap();
+        assert_eq!(game.title, "Physics");
+        assert_eq!(game.description.as_deref(), Some("Forces"));
+        assert!(matches!(
+            store.get_game(game_id + 1).await,
+            Err(TriviaError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn questions_keep_their_answers_and_game_order() {
+        let (store, admin_id) = store_with_admin();
+        let game_id = store.create_game("Mixed", None, admin_id).await.unwrap();
+
+        let answers = [
+            AnswerSpec::TrueFalse(true),
+            AnswerSpec::MultipleChoice {
+                choices: vec!["Mercury".to_string(), "Venus".to_string()],
+                correct_choice: 1,
+            },
+            AnswerSpec::FreeText {
+                accepted: vec!["Einstein".to_string()],
+                max_distance: 1,
+            },
+            AnswerSpec::Numeric {
+                target_value: 100.0,
+                tolerance: 5.0,
+                tolerance_percent: true,
+                unit: Some("km".to_string()),
+            },
+            AnswerSpec::Ordering {
+                items: vec!["One".to_string(), "Two".to_string(), "Three".to_string()],
+                partial_credit: true,
+            },
+        ];
+        let mut ids = Vec::new();
+        for (i, answer) in answers.iter().enumerate() {
+            let text = format!("Question {}", i + 1);
+            let question_id = store
+                .create_question_with_answer(&text, answer, admin_id)
+                .await
+                .unwrap();
+            let question = store.get_question(question_id).await.unwrap();
+            assert_eq!(question.kind, answer.kind());
+            assert_eq!(&store.get_answer_spec(&question).await.unwrap(), answer);
+            ids.push(question_id);
+        }
+
+        // Added back to front, so the game's order is the reverse of creation.
+        for (order, &question_id) in ids.iter().rev().enumerate() {
+            store
+                .add_question_to_game(game_id, question_id, order as i32 + 1)
+                .await
+                .unwrap();
+        }
+        let in_game: Vec<i32> = store
+            .get_game_questions(game_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|q| q.question_id)
+            .collect();
+        assert_eq!(in_game, ids.iter().rev().copied().collect::<Vec<_>>());
+
+        store.reorder_game_questions(game_id, &ids).await.unwrap();
+        let in_game: Vec<i32> = store
+            .get_game_questions(game_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|q| q.question_id)
+            .collect();
+        assert_eq!(in_game, ids);
+        assert!(matches!(
+            store.reorder_game_questions(game_id, &ids[1..]).await,
+            Err(TriviaError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            store
+                .reorder_game_questions(game_id, &[ids[0], ids[0], ids[1], ids[2], ids[3]])
+                .await,
+            Err(TriviaError::InvalidInput(_))
+        ));
+
+        store.delete_question(ids[0]).await.unwrap();
+        assert_eq!(store.get_game_questions(game_id).await.unwrap().len(), 4);
+        assert_eq!(
+            store.get_game_usage(game_id).await.unwrap().question_count,
+            4
+        );
+        store
+            .restore_item(TrashKind::Question, ids[0])
+            .await
+            .unwrap();
+        assert_eq!(store.get_game_questions(game_id).await.unwrap().len(), 5);
+        assert_eq!(
+            store.get_game_usage(game_id).await.unwrap().question_count,
+            5
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_questions_are_rejected() {
+        let (store, admin_id) = store_with_admin();
+
+        let one_choice = AnswerSpec::MultipleChoice {
+            choices: vec!["Only".to_string()],
+            correct_choice: 0,
+        };
+        assert!(matches!(
+            store
+                .create_question_with_answer("Pick one", &one_choice, admin_id)
+                .await,
+            Err(TriviaError::InvalidInput(_))
+        ));
+        assert!(store.get_all_questions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_new_game_is_saved_with_its_tags_or_not_at_all() {
+        let (store, admin_id) = store_with_admin();
+
+        let game_id = store
+            .create_game_with_tags(
+                "Space",
+                None,
+                &["Planets".to_string(), "stars".to_string()],
+                admin_id,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_game_tags(game_id).await.unwrap(),
+            ["planets", "stars"]
+        );
+
+        let too_long = ["x".repeat(crate::models::MAX_TAG_LEN + 1)];
+        assert!(matches!(
+            store
+                .create_game_with_tags("Oceans", None, &too_long, admin_id)
+                .await,
+            Err(TriviaError::InvalidInput(_))
+        ));
+        assert_eq!(store.get_games().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn a_game_edit_is_saved_with_its_tags_or_not_at_all() {
+        let (store, admin_id) = store_with_admin();
+        let game_id = store
+            .create_game_with_tags("Space", None, &["planets".to_string()], admin_id)
+            .await
+            .unwrap();
+
+        let too_long = ["x".repeat(crate::models::MAX_TAG_LEN + 1)];
+        assert!(matches!(
+            store
+                .update_game_with_tags(game_id, "Outer Space", None, Some(&too_long))
+                .await,
+            Err(TriviaError::InvalidInput(_))
+        ));
+        assert_eq!(store.get_game(game_id).await.unwrap().title, "Space");
+
+        store
+            .update_game_with_tags(game_id, "Outer Space", Some("Beyond the sky"), None)
+            .await
+            .unwrap();
+        assert_eq!(store.get_game_tags(game_id).await.unwrap(), ["planets"]);
+
+        store
+            .update_game_with_tags(game_id, "Outer Space", None, Some(&["Stars".to_string()]))
+            .await
+            .unwrap();
+        let game = store.get_game(game_id).await.unwrap();
+        assert_eq!(game.title, "Outer Space");
+        assert_eq!(game.description, None);
+        assert_eq!(store.get_game_tags(game_id).await.unwrap(), ["stars"]);
+    }
+
+    #[tokio::test]
+    async fn a_new_question_is_saved_with_its_details_or_not_at_all() {
+        let (store, admin_id) = store_with_admin();
+        let mut question = NewQuestion {
+            text: "Largest planet".to_string(),
+            answer: AnswerSpec::FreeText {
+                accepted: vec!["Jupiter".to_string()],
+                max_distance: 1,
+            },
+            difficulty: Difficulty::Hard,
+            time_limit_secs: Some(20),
+            explanation: Some("It is over 300 Earth masses.".to_string()),
+            source: None,
+            tags: vec!["Space".to_string(), "planets".to_string()],
+        };
+
+        let question_id = store
+            .create_question_with_details(&question, admin_id)
+            .await
+            .unwrap();
+        let saved = store.get_question(question_id).await.unwrap();
+        assert_eq!(saved.difficulty, Difficulty::Hard);
+        assert_eq!(saved.time_limit_secs, Some(20));
+        assert_eq!(saved.explanation, question.explanation);
+        assert_eq!(
+            store.get_answer_spec(&saved).await.unwrap(),
+            question.answer
+        );
+        assert_eq!(
+            store.get_question_tags(question_id).await.unwrap(),
+            ["planets", "space"]
+        );
+
+        question.text = "Smallest planet".to_string();
+        question.tags = vec!["x".repeat(crate::models::MAX_TAG_LEN + 1)];
+        assert!(matches!(
+            store
+                .create_question_with_details(&question, admin_id)
+                .await,
+            Err(TriviaError::InvalidInput(_))
+        ));
+        assert_eq!(store.get_all_questions().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn trashed_games_and_questions_cannot_be_used() {
+        let (store, admin_id) = store_with_admin();
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        let question_id = store
+            .create_question("Sky is blue", true, admin_id)
+            .await
+            .unwrap();
+
+        store.delete_question(question_id).await.unwrap();
+        assert!(matches!(
+            store.add_question_to_game(game_id, question_id, 1).await,
+            Err(TriviaError::NotFound(_))
+        ));
+
+        store.create_new_player("player", "secret").await.unwrap();
+        let player_id = store
+            .login_player("player", "secret")
+            .await
+            .unwrap()
+            .player_id;
+        store.delete_game(game_id).await.unwrap();
+        assert!(matches!(
+            store
+                .start_session(player_id, game_id, GameMode::Regular, None)
+                .await,
+            Err(TriviaError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn trashed_games_and_questions_cannot_be_edited() {
+        let (store, admin_id) = store_with_admin();
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        let question_id = store
+            .create_question("Sky is blue", true, admin_id)
+            .await
+            .unwrap();
+        store
+            .add_question_to_game(game_id, question_id, 1)
+            .await
+            .unwrap();
+        store.delete_question(question_id).await.unwrap();
+        store.delete_game(game_id).await.unwrap();
+
+        let results = [
+            store.update_game(game_id, "Renamed", None).await,
+            store
+                .set_game_play_order(game_id, PlayOrder::Shuffled, None)
+                .await,
+            store
+                .set_question_explanation(question_id, Some("Rayleigh scattering"), None)
+                .await,
+            store
+                .set_question_difficulty(question_id, Difficulty::Hard)
+                .await,
+            store.set_question_time_limit(question_id, Some(30)).await,
+            store.update_question_order(game_id, question_id, 2).await,
+        ];
+        for result in results {
+            assert!(matches!(result, Err(TriviaError::NotFound(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn a_new_miss_brings_a_practiced_question_back() {
+        let (store, admin_id) = store_with_admin();
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        let question_id = store
+            .create_question("Sky is blue", true, admin_id)
+            .await
+            .unwrap();
+        store
+            .add_question_to_game(game_id, question_id, 1)
+            .await
+            .unwrap();
+        store.create_new_player("player", "secret").await.unwrap();
+        let player_id = store
+            .login_player("player", "secret")
+            .await
+            .unwrap()
+            .player_id;
+
+        let now = Utc::now();
+        let mut card = PracticeCard::new(player_id, question_id, now - Duration::hours(1));
+        card.due_at = now + Duration::days(6);
+        store.save_practice_card(&card).await.unwrap();
+        assert!(store
+            .get_practice_questions(player_id, now, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let session_id = store
+            .start_session(player_id, game_id, GameMode::Regular, None)
+            .await
+            .unwrap();
+        store
+            .record_answer(session_id, question_id, "false", false, 0.0, 1000)
+            .await
+            .unwrap();
+        let queue = store
+            .get_practice_questions(player_id, Utc::now(), 10)
+            .await
+            .unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].question_id, question_id);
+
+        // Practicing it again clears the miss.
+        card.reviewed_at = Utc::now() + Duration::minutes(1);
+        store.save_practice_card(&card).await.unwrap();
+        assert!(store
+            .get_practice_questions(player_id, Utc::now(), 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn finished_sessions_rank_on_the_leaderboard() {
+        let (store, admin_id) = store_with_admin();
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        let question_id = store
+            .create_question("Sky is blue", true, admin_id)
+            .await
+            .unwrap();
+        store
+            .add_question_to_game(game_id, question_id, 1)
+            .await
+            .unwrap();
+
+        for (username, correct) in [("alice", true), ("bob", false)] {
+            store.create_new_player(username, "secret").await.unwrap();
+            let player = store.login_player(username, "secret").await.unwrap();
+            let session_id = store
+                .start_session(player.player_id, game_id, GameMode::Regular, None)
+                .await
+                .unwrap();
+            let score = if correct { 1.0 } else { 0.0 };
+            store
+                .record_answer(session_id, question_id, "true", correct, score, 1000)
+                .await
+                .unwrap();
+            store
+                .finish_session(session_id, correct as i32, 1, score, 1.0, 0.0)
+                .await
+                .unwrap();
+        }
+
+        let board = store
+            .get_game_leaderboard(game_id, GameMode::Regular, LeaderboardBasis::Best, 10)
+            .await
+            .unwrap();
+        let names: Vec<&str> = board.iter().map(|e| e.username.as_str()).collect();
+        assert_eq!(names, ["alice", "bob"]);
+        assert!(store
+            .get_game_leaderboard(game_id, GameMode::Timed, LeaderboardBasis::Best, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,39 @@
+use super::MigrationStatus;
+use crate::error::Result;
+use sqlx::migrate::{Migrate, Migrator};
+use std::collections::HashSet;
+
+pub(crate) async fn status<C: Migrate>(
+    conn: &mut C,
+    migrator: &Migrator,
+) -> Result<Vec<MigrationStatus>> {
+    conn.ensure_migrations_table()
+        .await
+        .map_err(sqlx::Error::from)?;
+
+    let applied: HashSet<i64> = conn
+        .list_applied_migrations()
+        .await
+        .map_err(sqlx::Error::from)?
+        .into_iter()
+        .map(|m| m.version)
+        .collect();
+
+    Ok(migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| MigrationStatus {
+            version: m.version,
+            description: m.description.to_string(),
+            applied: applied.contains(&m.version),
+        })
+        .collect())
+}
+
+/// Returns `(latest, target)` where `latest` is the newest applied migration
+/// and `target` is the version to undo back to so only `latest` is reverted.
+pub(crate) fn undo_target(status: &[MigrationStatus]) -> Option<(i64, i64)> {
+    let mut applied = status.iter().filter(|m| m.applied).map(|m| m.version);
+    let latest = applied.next_back()?;
+    Some((latest, applied.next_back().unwrap_or(0)))
+}
@@ -0,0 +1,680 @@
+use crate::error::{Result, TriviaError};
+use crate::matching;
+use crate::models::{
+    normalize_tag, AcceptedAnswer, Admin, AnswerSpec, Difficulty, Game, GameMode, GameQuestionFull,
+    GameSession, GameUsage, LeaderboardBasis, LeaderboardEntry, NewQuestion, NumericAnswer,
+    OrderingItem, PlayOrder, Player, PracticeCard, Question, QuestionChoice, QuestionKind,
+    QuestionRevision, QuestionSearch, QuestionUsage, SessionAnswer, SurvivalScore, Tag, TrashItem,
+    TrashKind, MAX_CHOICES, MAX_ORDERING_ITEMS, MAX_TAG_LEN, MAX_TIME_LIMIT_SECS, MIN_CHOICES,
+    MIN_ORDERING_ITEMS, MIN_TIME_LIMIT_SECS,
+};
+use chrono::{DateTime, Utc};
+
+pub mod memory;
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+mod migrations;
+#[cfg(feature = "mysql")]
+pub mod mysql;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use memory::MemoryStore;
+#[cfg(feature = "mysql")]
+pub use mysql::MySqlStore;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+
+/// One schema migration compiled into the binary.
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+}
+
+/// Storage operations used by `GameManager`.
+///
+/// Implemented for MariaDB/MySQL by `MySqlStore` (`mysql` feature), for SQLite
+/// by `SqliteStore` (`sqlite` feature) and for tests and demos by the
+/// in-memory [`MemoryStore`].
+// The CLI drives a single store from one task, so the futures don't need to be `Send`.
+#[allow(async_fn_in_trait)]
+pub trait TriviaStore {
+    // Schema
+    /// Lists every migration compiled into the binary and whether it is applied.
+    async fn migration_status(&self) -> Result<Vec<MigrationStatus>>;
+    /// Applies all pending migrations.
+    async fn migrate_up(&self) -> Result<()>;
+    /// Reverts the most recently applied migration and returns its version.
+    async fn migrate_down(&self) -> Result<Option<i64>>;
+
+    // Games
+    async fn get_games(&self) -> Result<Vec<Game>>;
+    async fn create_game(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        admin_id: i32,
+    ) -> Result<i32>;
+    /// Creates a game and links its tags in one transaction, so a tag that
+    /// fails validation stores no game.
+    async fn create_game_with_tags(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        tags: &[String],
+        admin_id: i32,
+    ) -> Result<i32>;
+    async fn get_game(&self, game_id: i32) -> Result<Game>;
+    async fn update_game(&self, game_id: i32, title: &str, description: Option<&str>)
+        -> Result<()>;
+    /// Updates a game's title and description and, when `tags` is given,
+    /// replaces its tags, all in one transaction.
+    async fn update_game_with_tags(
+        &self,
+        game_id: i32,
+        title: &str,
+        description: Option<&str>,
+        tags: Option<&[String]>,
+    ) -> Result<()>;
+    /// Sets how the game's questions are ordered each play. `draw_count` is
+    /// required for [`PlayOrder::Draw`] and must be `None` otherwise.
+    async fn set_game_play_order(
+        &self,
+        game_id: i32,
+        play_order: PlayOrder,
+        draw_count: Option<i32>,
+    ) -> Result<()>;
+    /// Counts a game's questions and recorded sessions.
+    async fn get_game_usage(&self, game_id: i32) -> Result<GameUsage>;
+    /// Moves a game to the trash. It is left out of game lists and
+    /// leaderboards until it is restored.
+    async fn delete_game(&self, game_id: i32) -> Result<()>;
+
+    // Questions
+    async fn get_all_questions(&self) -> Result<Vec<Question>>;
+    /// Lists every question with the number of games and recorded answers
+    /// that deleting it would affect.
+    async fn get_question_usage(&self) -> Result<Vec<QuestionUsage>>;
+    /// Returns the games that include a question, by title.
+    async fn get_games_using_question(&self, question_id: i32) -> Result<Vec<Game>>;
+    /// Finds live questions matching every filter in `search`, best keyword
+    /// matches first, then by ID.
+    async fn search_questions(&self, search: &QuestionSearch, limit: i32) -> Result<Vec<Question>>;
+    async fn create_question(&self, text: &str, answer: bool, admin_id: i32) -> Result<i32>;
+    /// Creates a multiple-choice question; `correct_choice` indexes `choices`.
+    async fn create_multiple_choice_question(
+        &self,
+        text: &str,
+        choices: &[String],
+        correct_choice: usize,
+        admin_id: i32,
+    ) -> Result<i32>;
+    /// Returns a question's choices in display order.
+    async fn get_question_choices(&self, question_id: i32) -> Result<Vec<QuestionChoice>>;
+    /// Creates a free-text question; every entry of `accepted` is a correct
+    /// response, each forgiving up to `max_distance` typos.
+    async fn create_free_text_question(
+        &self,
+        text: &str,
+        accepted: &[String],
+        max_distance: i32,
+        admin_id: i32,
+    ) -> Result<i32>;
+    async fn get_accepted_answers(&self, question_id: i32) -> Result<Vec<AcceptedAnswer>>;
+    /// Creates a numeric question. `tolerance` is a percentage of
+    /// `target_value` when `tolerance_percent` is set, otherwise absolute.
+    async fn create_numeric_question(
+        &self,
+        text: &str,
+        target_value: f64,
+        tolerance: f64,
+        tolerance_percent: bool,
+        unit: Option<&str>,
+        admin_id: i32,
+    ) -> Result<i32>;
+    async fn get_numeric_answer(&self, question_id: i32) -> Result<NumericAnswer>;
+    /// Creates an ordering question from `items` listed in their correct order.
+    async fn create_ordering_question(
+        &self,
+        text: &str,
+        items: &[String],
+        partial_credit: bool,
+        admin_id: i32,
+    ) -> Result<i32>;
+    /// Returns a question's items in their correct order.
+    async fn get_ordering_items(&self, question_id: i32) -> Result<Vec<OrderingItem>>;
+    /// Sets or clears the explanation and source shown after a question.
+    async fn set_question_explanation(
+        &self,
+        question_id: i32,
+        explanation: Option<&str>,
+        source: Option<&str>,
+    ) -> Result<()>;
+    /// Sets how hard a question is, and so how many points it is worth.
+    async fn set_question_difficulty(&self, question_id: i32, difficulty: Difficulty)
+        -> Result<()>;
+    /// Sets how long a question may be answered in timed mode, or clears it
+    /// so the configured default applies.
+    async fn set_question_time_limit(&self, question_id: i32, seconds: Option<i32>) -> Result<()>;
+    /// Moves a question to the trash. It is hidden from the question bank and
+    /// from the games that use it until it is restored.
+    async fn delete_question(&self, question_id: i32) -> Result<()>;
+    async fn get_question(&self, question_id: i32) -> Result<Question>;
+    /// Replaces a question's text, answer, explanation and source, first saving
+    /// the current version as a revision by `admin_id`. An edit that changes
+    /// nothing records no revision, and a question cannot change its kind.
+    async fn update_question(
+        &self,
+        question_id: i32,
+        text: &str,
+        answer: &AnswerSpec,
+        explanation: Option<&str>,
+        source: Option<&str>,
+        admin_id: i32,
+    ) -> Result<()>;
+    /// Returns a question's earlier versions, newest first.
+    async fn get_question_revisions(&self, question_id: i32) -> Result<Vec<QuestionRevision>>;
+
+    /// Creates a question with its answer, difficulty, time limit, explanation,
+    /// source and tags. Everything is checked first and stored in one
+    /// transaction, so an invalid field stores nothing.
+    async fn create_question_with_details(
+        &self,
+        question: &NewQuestion,
+        admin_id: i32,
+    ) -> Result<i32>;
+
+    /// Creates a question of whichever kind `answer` describes.
+    async fn create_question_with_answer(
+        &self,
+        text: &str,
+        answer: &AnswerSpec,
+        admin_id: i32,
+    ) -> Result<i32> {
+        match answer {
+            AnswerSpec::TrueFalse(answer) => self.create_question(text, *answer, admin_id).await,
+            AnswerSpec::MultipleChoice {
+                choices,
+                correct_choice,
+            } => {
+                self.create_multiple_choice_question(text, choices, *correct_choice, admin_id)
+                    .await
+            }
+            AnswerSpec::FreeText {
+                accepted,
+                max_distance,
+            } => {
+                self.create_free_text_question(text, accepted, *max_distance, admin_id)
+                    .await
+            }
+            AnswerSpec::Numeric {
+                target_value,
+                tolerance,
+                tolerance_percent,
+                unit,
+            } => {
+                self.create_numeric_question(
+                    text,
+                    *target_value,
+                    *tolerance,
+                    *tolerance_percent,
+                    unit.as_deref(),
+                    admin_id,
+                )
+                .await
+            }
+            AnswerSpec::Ordering {
+                items,
+                partial_credit,
+            } => {
+                self.create_ordering_question(text, items, *partial_credit, admin_id)
+                    .await
+            }
+        }
+    }
+
+    /// Loads a question's current answer in the form it is edited in.
+    async fn get_answer_spec(&self, question: &Question) -> Result<AnswerSpec> {
+        let question_id = question.question_id;
+        Ok(match question.kind {
+            QuestionKind::TrueFalse => AnswerSpec::TrueFalse(question.correct_answer.to_bool()),
+            QuestionKind::MultipleChoice => {
+                let choices = self.get_question_choices(question_id).await?;
+                AnswerSpec::MultipleChoice {
+                    correct_choice: choices.iter().position(|c| c.is_correct).unwrap_or(0),
+                    choices: choices.into_iter().map(|c| c.choice_text).collect(),
+                }
+            }
+            QuestionKind::FreeText => {
+                let accepted = self.get_accepted_answers(question_id).await?;
+                AnswerSpec::FreeText {
+                    max_distance: accepted.first().map_or(0, |a| a.max_distance),
+                    accepted: accepted.into_iter().map(|a| a.answer_text).collect(),
+                }
+            }
+            QuestionKind::Numeric => {
+                let numeric = self.get_numeric_answer(question_id).await?;
+                AnswerSpec::Numeric {
+                    target_value: numeric.target_value,
+                    tolerance: numeric.tolerance,
+                    tolerance_percent: numeric.tolerance_percent,
+                    unit: numeric.unit,
+                }
+            }
+            QuestionKind::Ordering => {
+                let items = self.get_ordering_items(question_id).await?;
+                AnswerSpec::Ordering {
+                    items: items.into_iter().map(|i| i.item_text).collect(),
+                    partial_credit: question.partial_credit,
+                }
+            }
+        })
+    }
+
+    /// Restores a question to one of its revisions. The version it replaces is
+    /// saved as a new revision, so a revert can itself be reverted.
+    async fn revert_question(
+        &self,
+        question_id: i32,
+        revision_id: i32,
+        admin_id: i32,
+    ) -> Result<()> {
+        let revision = self
+            .get_question_revisions(question_id)
+            .await?
+            .into_iter()
+            .find(|r| r.revision_id == revision_id)
+            .ok_or_else(|| TriviaError::NotFound(format!("Revision {}", revision_id)))?;
+        let answer = AnswerSpec::from_json(&revision.answer)?;
+
+        self.update_question(
+            question_id,
+            &revision.question_text,
+            &answer,
+            revision.explanation.as_deref(),
+            revision.source.as_deref(),
+            admin_id,
+        )
+        .await
+    }
+
+    // Game questions
+    async fn get_game_questions(&self, game_id: i32) -> Result<Vec<GameQuestionFull>>;
+    async fn add_question_to_game(&self, game_id: i32, question_id: i32, order: i32) -> Result<()>;
+    /// Removes a question from a game and closes the gap in `question_order`.
+    async fn delete_game_question(&self, game_id: i32, question_id: i32) -> Result<()>;
+    async fn update_question_order(&self, game_id: i32, question_id: i32, order: i32)
+        -> Result<()>;
+    /// Puts a game's questions in the order of `question_ids`, which must list
+    /// each of them exactly once. All positions change in one transaction.
+    async fn reorder_game_questions(&self, game_id: i32, question_ids: &[i32]) -> Result<()>;
+    async fn get_max_question_order(&self, game_id: i32) -> Result<i32>;
+
+    // Game sessions
+    /// Starts a session, recording the seed its questions were arranged with.
+    async fn start_session(
+        &self,
+        player_id: i32,
+        game_id: i32,
+        mode: GameMode,
+        seed: Option<i64>,
+    ) -> Result<i32>;
+    async fn record_answer(
+        &self,
+        session_id: i32,
+        question_id: i32,
+        submitted_answer: &str,
+        is_correct: bool,
+        score: f64,
+        response_time_ms: i32,
+    ) -> Result<()>;
+    /// Stamps `ended_at` and stores the final score out of `max_score` points,
+    /// with any speed bonus kept apart in `bonus`.
+    async fn finish_session(
+        &self,
+        session_id: i32,
+        correct_answers: i32,
+        total_questions: i32,
+        score: f64,
+        max_score: f64,
+        bonus: f64,
+    ) -> Result<()>;
+    async fn get_session(&self, session_id: i32) -> Result<GameSession>;
+    /// Returns the player's sessions, newest first.
+    async fn get_player_sessions(&self, player_id: i32) -> Result<Vec<GameSession>>;
+    async fn get_session_answers(&self, session_id: i32) -> Result<Vec<SessionAnswer>>;
+
+    // Leaderboards
+    /// Ranks players on one game using one finished session each, counting
+    /// only sessions played in `mode`.
+    async fn get_game_leaderboard(
+        &self,
+        game_id: i32,
+        mode: GameMode,
+        basis: LeaderboardBasis,
+        limit: i32,
+    ) -> Result<Vec<LeaderboardEntry>>;
+    /// Ranks players by their totals across every game they have finished in
+    /// `mode`.
+    async fn get_global_leaderboard(
+        &self,
+        mode: GameMode,
+        basis: LeaderboardBasis,
+        limit: i32,
+    ) -> Result<Vec<LeaderboardEntry>>;
+
+    // Survival
+    /// Stores a finished survival run. `game_id` is `None` for a run through
+    /// the shuffled pool of every game.
+    async fn record_survival_run(
+        &self,
+        player_id: i32,
+        game_id: Option<i32>,
+        lives: i32,
+        questions_answered: i32,
+        correct_answers: i32,
+        score: f64,
+    ) -> Result<i32>;
+    /// Ranks players by their best run on one game, or on the pool of every
+    /// game, counting only runs played with the same number of lives.
+    async fn get_survival_high_scores(
+        &self,
+        game_id: Option<i32>,
+        lives: i32,
+        limit: i32,
+    ) -> Result<Vec<SurvivalScore>>;
+
+    // Practice
+    /// Lists up to `limit` questions the player has to practice: questions
+    /// whose card is due by `now`, or that the player has missed in a game
+    /// since their last practice, and questions they have missed in a game
+    /// but never practiced. The longest-waiting come first, and
+    /// `question_order` is the position in that queue.
+    async fn get_practice_questions(
+        &self,
+        player_id: i32,
+        now: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<GameQuestionFull>>;
+    /// Returns the player's card for a question, or `None` if they haven't
+    /// practiced it yet.
+    async fn get_practice_card(
+        &self,
+        player_id: i32,
+        question_id: i32,
+    ) -> Result<Option<PracticeCard>>;
+    /// Stores a card, replacing the player's previous card for the question.
+    async fn save_practice_card(&self, card: &PracticeCard) -> Result<()>;
+    /// Returns when the player's next card falls due, if they have any.
+    async fn get_next_practice_due(&self, player_id: i32) -> Result<Option<DateTime<Utc>>>;
+
+    // Players
+    async fn login_player(&self, username: &str, password: &str) -> Result<Player>;
+    async fn get_all_players(&self) -> Result<Vec<Player>>;
+    async fn create_new_player(&self, username: &str, password: &str) -> Result<()>;
+    /// Moves a player to the trash. They cannot log in and are left off the
+    /// leaderboards until they are restored.
+    async fn delete_player(&self, player_id: i32) -> Result<()>;
+
+    // Tags
+    /// Lists every tag by name, with how many live questions and games use it.
+    async fn get_tags(&self) -> Result<Vec<Tag>>;
+    /// Returns a question's tag names, alphabetically.
+    async fn get_question_tags(&self, question_id: i32) -> Result<Vec<String>>;
+    /// Replaces a question's tags, creating any tag that doesn't exist yet.
+    async fn set_question_tags(&self, question_id: i32, tags: &[String]) -> Result<()>;
+    /// Returns a game's tag names, alphabetically.
+    async fn get_game_tags(&self, game_id: i32) -> Result<Vec<String>>;
+    /// Replaces a game's tags, creating any tag that doesn't exist yet.
+    async fn set_game_tags(&self, game_id: i32, tags: &[String]) -> Result<()>;
+    async fn get_questions_by_tag(&self, tag: &str) -> Result<Vec<Question>>;
+    async fn get_games_by_tag(&self, tag: &str) -> Result<Vec<Game>>;
+
+    // Trash
+    /// Lists the players, games and questions in the trash, newest first.
+    async fn get_trash(&self) -> Result<Vec<TrashItem>>;
+    async fn restore_item(&self, kind: TrashKind, id: i32) -> Result<()>;
+    /// Permanently deletes something already in the trash, along with
+    /// everything that depends on it.
+    async fn purge_item(&self, kind: TrashKind, id: i32) -> Result<()>;
+
+    // Admins
+    async fn login_admin(&self, username: &str, password: &str) -> Result<Admin>;
+}
+
+/// Checks the shape of a multiple-choice question before it is stored.
+fn validate_choices(choices: &[String], correct_choice: usize) -> Result<()> {
+    if !(MIN_CHOICES..=MAX_CHOICES).contains(&choices.len()) {
+        return Err(TriviaError::InvalidInput(format!(
+            "a multiple-choice question needs {} to {} choices",
+            MIN_CHOICES, MAX_CHOICES
+        )));
+    }
+    if choices.iter().any(|c| c.trim().is_empty()) {
+        return Err(TriviaError::InvalidInput(
+            "choices cannot be empty".to_string(),
+        ));
+    }
+    if correct_choice >= choices.len() {
+        return Err(TriviaError::InvalidInput(format!(
+            "choice {} does not exist",
+            correct_choice + 1
+        )));
+    }
+    Ok(())
+}
+
+/// Checks the accepted answers of a free-text question before they are stored.
+fn validate_accepted_answers(accepted: &[String], max_distance: i32) -> Result<()> {
+    if accepted.is_empty() {
+        return Err(TriviaError::InvalidInput(
+            "a free-text question needs at least one accepted answer".to_string(),
+        ));
+    }
+    if let Some(answer) = accepted.iter().find(|a| matching::normalize(a).is_empty()) {
+        return Err(TriviaError::InvalidInput(format!(
+            "'{}' has no letters or digits to match against",
+            answer
+        )));
+    }
+    if max_distance < 0 {
+        return Err(TriviaError::InvalidInput(
+            "the typo allowance cannot be negative".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks the items of an ordering question before they are stored.
+fn validate_ordering_items(items: &[String]) -> Result<()> {
+    if !(MIN_ORDERING_ITEMS..=MAX_ORDERING_ITEMS).contains(&items.len()) {
+        return Err(TriviaError::InvalidInput(format!(
+            "an ordering question needs {} to {} items",
+            MIN_ORDERING_ITEMS, MAX_ORDERING_ITEMS
+        )));
+    }
+    if items.iter().any(|i| i.trim().is_empty()) {
+        return Err(TriviaError::InvalidInput(
+            "items cannot be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Longest unit `numeric_answers.unit` can hold.
+const MAX_UNIT_LEN: usize = 20;
+
+/// Checks the target of a numeric question before it is stored.
+fn validate_numeric_answer(target_value: f64, tolerance: f64, unit: Option<&str>) -> Result<()> {
+    if !target_value.is_finite() {
+        return Err(TriviaError::InvalidInput(
+            "the answer must be a finite number".to_string(),
+        ));
+    }
+    if !tolerance.is_finite() || tolerance < 0.0 {
+        return Err(TriviaError::InvalidInput(
+            "the tolerance must be zero or a positive number".to_string(),
+        ));
+    }
+    if unit.is_some_and(|u| u.chars().count() > MAX_UNIT_LEN) {
+        return Err(TriviaError::InvalidInput(format!(
+            "units are limited to {} characters",
+            MAX_UNIT_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Checks any question's answer before it is stored.
+fn validate_answer(answer: &AnswerSpec) -> Result<()> {
+    match answer {
+        AnswerSpec::TrueFalse(_) => Ok(()),
+        AnswerSpec::MultipleChoice {
+            choices,
+            correct_choice,
+        } => validate_choices(choices, *correct_choice),
+        AnswerSpec::FreeText {
+            accepted,
+            max_distance,
+        } => validate_accepted_answers(accepted, *max_distance),
+        AnswerSpec::Numeric {
+            target_value,
+            tolerance,
+            unit,
+            ..
+        } => validate_numeric_answer(*target_value, *tolerance, unit.as_deref()),
+        AnswerSpec::Ordering { items, .. } => validate_ordering_items(items),
+    }
+}
+
+/// Checks a new question before it is stored and returns its normalized tags.
+fn validate_new_question(question: &NewQuestion) -> Result<Vec<String>> {
+    if question.text.trim().is_empty() {
+        return Err(TriviaError::InvalidInput(
+            "question text cannot be empty".to_string(),
+        ));
+    }
+    validate_answer(&question.answer)?;
+    validate_time_limit(question.time_limit_secs)?;
+    normalize_tags(&question.tags)
+}
+
+/// Checks an edit against the question it replaces and reports whether it
+/// changes anything.
+fn check_edit(
+    current: &Question,
+    current_answer: &AnswerSpec,
+    text: &str,
+    answer: &AnswerSpec,
+    explanation: Option<&str>,
+    source: Option<&str>,
+) -> Result<bool> {
+    if answer.kind() != current.kind {
+        return Err(TriviaError::InvalidInput(format!(
+            "a {} question cannot become a {} question",
+            current.kind,
+            answer.kind()
+        )));
+    }
+    if text.trim().is_empty() {
+        return Err(TriviaError::InvalidInput(
+            "question text cannot be empty".to_string(),
+        ));
+    }
+    validate_answer(answer)?;
+
+    Ok(text != current.question_text
+        || answer != current_answer
+        || explanation != current.explanation.as_deref()
+        || source != current.source.as_deref())
+}
+
+/// Checks that `question_ids` lists each of a game's questions exactly once.
+fn check_question_order(current: &[GameQuestionFull], question_ids: &[i32]) -> Result<()> {
+    let mut expected: Vec<i32> = current.iter().map(|q| q.question_id).collect();
+    let mut given = question_ids.to_vec();
+    expected.sort_unstable();
+    given.sort_unstable();
+    if expected != given {
+        return Err(TriviaError::InvalidInput(format!(
+            "the new order must list each of the game's {} questions once",
+            current.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Checks a game's play order before it is stored.
+fn validate_play_order(play_order: PlayOrder, draw_count: Option<i32>) -> Result<()> {
+    match (play_order, draw_count) {
+        (PlayOrder::Draw, Some(count)) if count >= 1 => Ok(()),
+        (PlayOrder::Draw, _) => Err(TriviaError::InvalidInput(
+            "a random draw needs at least one question".to_string(),
+        )),
+        (_, Some(_)) => Err(TriviaError::InvalidInput(
+            "only a random draw has a question count".to_string(),
+        )),
+        (_, None) => Ok(()),
+    }
+}
+
+/// Checks a question's timed-mode limit before it is stored.
+fn validate_time_limit(seconds: Option<i32>) -> Result<()> {
+    match seconds {
+        Some(seconds) if !(MIN_TIME_LIMIT_SECS..=MAX_TIME_LIMIT_SECS).contains(&seconds) => {
+            Err(TriviaError::InvalidInput(format!(
+                "time limits must be {} to {} seconds",
+                MIN_TIME_LIMIT_SECS, MAX_TIME_LIMIT_SECS
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Normalizes tag names for storage, dropping repeats, and checks they fit.
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|t| normalize_tag(t)) {
+        if tag.is_empty() {
+            return Err(TriviaError::InvalidInput(
+                "tags cannot be empty".to_string(),
+            ));
+        }
+        if tag.chars().count() > MAX_TAG_LEN {
+            return Err(TriviaError::InvalidInput(format!(
+                "tags are limited to {} characters",
+                MAX_TAG_LEN
+            )));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Shortest word a keyword search looks for. MariaDB's full-text index skips
+/// anything shorter, so the other backends ignore those words too.
+const MIN_SEARCH_TERM_LEN: usize = 3;
+
+/// Splits a search's keyword into the lowercase words every match must
+/// start a word with, or `None` if the search has no keyword.
+fn search_terms(search: &QuestionSearch) -> Result<Option<Vec<String>>> {
+    let Some(keyword) = &search.keyword else {
+        return Ok(None);
+    };
+
+    let terms: Vec<String> = keyword
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= MIN_SEARCH_TERM_LEN)
+        .map(str::to_lowercase)
+        .collect();
+    if terms.is_empty() {
+        return Err(TriviaError::InvalidInput(format!(
+            "search keywords need at least {} letters",
+            MIN_SEARCH_TERM_LEN
+        )));
+    }
+    Ok(Some(terms))
+}
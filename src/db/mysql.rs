@@ -0,0 +1,1984 @@
+use super::{
+    check_edit, check_question_order, migrations, normalize_tags, search_terms,
+    validate_accepted_answers, validate_choices, validate_new_question, validate_numeric_answer,
+    validate_ordering_items, validate_play_order, validate_time_limit, MigrationStatus,
+    TriviaStore,
+};
+use crate::error::{is_foreign_key_violation, is_unique_violation, Result, TriviaError};
+use crate::models::{
+    normalize_tag, AcceptedAnswer, Admin, Answer, AnswerSpec, Difficulty, Game, GameMode,
+    GameQuestionFull, GameSession, GameUsage, LeaderboardBasis, LeaderboardEntry, NewQuestion,
+    NumericAnswer, OrderingItem, PlayOrder, Player, PracticeCard, Question, QuestionChoice,
+    QuestionKind, QuestionRevision, QuestionSearch, QuestionUsage, SessionAnswer, SurvivalScore,
+    Tag, TrashItem, TrashKind,
+};
+use crate::password::{hash_password, verify_password, PasswordCheck};
+use chrono::{DateTime, Utc};
+use sqlx::migrate::Migrator;
+use sqlx::mysql::{MySql, MySqlPool};
+use sqlx::Transaction;
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations/mysql");
+
+pub struct MySqlStore {
+    pool: MySqlPool,
+}
+
+impl MySqlStore {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    /// Closes any gaps in a game's `question_order`.
+    async fn renumber_game_questions(&self, game_id: i32) -> Result<()> {
+        let questions = self.get_game_questions(game_id).await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        for (new_order, question) in questions.iter().enumerate() {
+            let new_order = (new_order + 1) as i32;
+            if new_order != question.question_order {
+                sqlx::query!(
+                    r#"
+                    UPDATE game_questions
+                    SET question_order = ?
+                    WHERE game_id = ? AND question_id = ?
+                    "#,
+                    new_order,
+                    game_id,
+                    question.question_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Links already normalized tags to a question, creating missing tags first.
+    async fn link_question_tags(
+        tx: &mut Transaction<'_, MySql>,
+        question_id: i32,
+        tags: &[String],
+    ) -> Result<()> {
+        for tag in tags {
+            sqlx::query!("INSERT IGNORE INTO tags (name) VALUES (?)", tag)
+                .execute(&mut **tx)
+                .await?;
+            sqlx::query!(
+                r#"
+                INSERT INTO question_tags (question_id, tag_id)
+                SELECT ?, tag_id FROM tags WHERE name = ?
+                "#,
+                question_id,
+                tag
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Links already normalized tags to a game, creating missing tags first.
+    async fn link_game_tags(
+        tx: &mut Transaction<'_, MySql>,
+        game_id: i32,
+        tags: &[String],
+    ) -> Result<()> {
+        for tag in tags {
+            sqlx::query!("INSERT IGNORE INTO tags (name) VALUES (?)", tag)
+                .execute(&mut **tx)
+                .await?;
+            sqlx::query!(
+                r#"
+                INSERT INTO game_tags (game_id, tag_id)
+                SELECT ?, tag_id FROM tags WHERE name = ?
+                "#,
+                game_id,
+                tag
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Inserts the choices, accepted answers, numeric target or items that
+    /// `answer` describes.
+    async fn insert_answer_rows(
+        tx: &mut Transaction<'_, MySql>,
+        question_id: i32,
+        answer: &AnswerSpec,
+    ) -> Result<()> {
+        match answer {
+            AnswerSpec::TrueFalse(_) => {}
+            AnswerSpec::MultipleChoice {
+                choices,
+                correct_choice,
+            } => {
+                for (i, choice) in choices.iter().enumerate() {
+                    sqlx::query!(
+                        r#"
+                        INSERT INTO question_choices (question_id, choice_order, choice_text, is_correct)
+                        VALUES (?, ?, ?, ?)
+                        "#,
+                        question_id,
+                        i as i32 + 1,
+                        choice.trim(),
+                        i == *correct_choice
+                    )
+                    .execute(&mut **tx)
+                    .await?;
+                }
+            }
+            AnswerSpec::FreeText {
+                accepted,
+                max_distance,
+            } => {
+                for answer in accepted {
+                    sqlx::query!(
+                        r#"
+                        INSERT INTO accepted_answers (question_id, answer_text, max_distance)
+                        VALUES (?, ?, ?)
+                        "#,
+                        question_id,
+                        answer.trim(),
+                        max_distance
+                    )
+                    .execute(&mut **tx)
+                    .await?;
+                }
+            }
+            AnswerSpec::Numeric {
+                target_value,
+                tolerance,
+                tolerance_percent,
+                unit,
+            } => {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO numeric_answers
+                        (question_id, target_value, tolerance, tolerance_percent, unit)
+                    VALUES (?, ?, ?, ?, ?)
+                    "#,
+                    question_id,
+                    target_value,
+                    tolerance,
+                    tolerance_percent,
+                    unit
+                )
+                .execute(&mut **tx)
+                .await?;
+            }
+            AnswerSpec::Ordering { items, .. } => {
+                for (i, item) in items.iter().enumerate() {
+                    sqlx::query!(
+                        r#"
+                        INSERT INTO ordering_items (question_id, item_order, item_text)
+                        VALUES (?, ?, ?)
+                        "#,
+                        question_id,
+                        i as i32 + 1,
+                        item.trim()
+                    )
+                    .execute(&mut **tx)
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes whatever kind-specific answer rows a question has.
+    async fn delete_answer_rows(tx: &mut Transaction<'_, MySql>, question_id: i32) -> Result<()> {
+        sqlx::query!(
+            "DELETE FROM question_choices WHERE question_id = ?",
+            question_id
+        )
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM accepted_answers WHERE question_id = ?",
+            question_id
+        )
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM numeric_answers WHERE question_id = ?",
+            question_id
+        )
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM ordering_items WHERE question_id = ?",
+            question_id
+        )
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+impl TriviaStore for MySqlStore {
+    async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        let mut conn = self.pool.acquire().await?;
+        migrations::status(&mut *conn, &MIGRATOR).await
+    }
+
+    async fn migrate_up(&self) -> Result<()> {
+        MIGRATOR.run(&self.pool).await.map_err(sqlx::Error::from)?;
+        Ok(())
+    }
+
+    async fn migrate_down(&self) -> Result<Option<i64>> {
+        let status = self.migration_status().await?;
+        let Some((latest, target)) = migrations::undo_target(&status) else {
+            return Ok(None);
+        };
+
+        MIGRATOR
+            .undo(&self.pool, target)
+            .await
+            .map_err(sqlx::Error::from)?;
+        Ok(Some(latest))
+    }
+
+    async fn get_games(&self) -> Result<Vec<Game>> {
+        sqlx::query_as!(
+            Game,
+            r#"
+            SELECT game_id, title, description,
+                   play_order as "play_order: PlayOrder", draw_count
+            FROM games
+            WHERE deleted_at IS NULL
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_trash(&self) -> Result<Vec<TrashItem>> {
+        sqlx::query_as!(
+            TrashItem,
+            r#"
+            SELECT 'player' as "kind!: TrashKind", player_id as "id!: i32",
+                   username as "name!: String", deleted_at
+            FROM players
+            WHERE deleted_at IS NOT NULL
+            UNION ALL
+            SELECT 'game', game_id, title, deleted_at
+            FROM games
+            WHERE deleted_at IS NOT NULL
+            UNION ALL
+            SELECT 'question', question_id, question_text, deleted_at
+            FROM questions
+            WHERE deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn restore_item(&self, kind: TrashKind, id: i32) -> Result<()> {
+        let result = match kind {
+            TrashKind::Player => sqlx::query!(
+                "UPDATE players SET deleted_at = NULL WHERE player_id = ? AND deleted_at IS NOT NULL",
+                id
+            ),
+            TrashKind::Game => sqlx::query!(
+                "UPDATE games SET deleted_at = NULL WHERE game_id = ? AND deleted_at IS NOT NULL",
+                id
+            ),
+            TrashKind::Question => sqlx::query!(
+                "UPDATE questions SET deleted_at = NULL WHERE question_id = ? AND deleted_at IS NOT NULL",
+                id
+            ),
+        }
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!(
+                "{} {} in the trash",
+                kind, id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn purge_item(&self, kind: TrashKind, id: i32) -> Result<()> {
+        let result = match kind {
+            TrashKind::Player => sqlx::query!(
+                "DELETE FROM players WHERE player_id = ? AND deleted_at IS NOT NULL",
+                id
+            ),
+            TrashKind::Game => sqlx::query!(
+                "DELETE FROM games WHERE game_id = ? AND deleted_at IS NOT NULL",
+                id
+            ),
+            TrashKind::Question => sqlx::query!(
+                "DELETE FROM questions WHERE question_id = ? AND deleted_at IS NOT NULL",
+                id
+            ),
+        }
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!(
+                "{} {} in the trash",
+                kind, id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn get_tags(&self) -> Result<Vec<Tag>> {
+        sqlx::query_as!(
+            Tag,
+            r#"
+            SELECT t.tag_id, t.name,
+                   (SELECT COUNT(*) FROM question_tags qt
+                    JOIN questions q ON q.question_id = qt.question_id
+                    WHERE qt.tag_id = t.tag_id AND q.deleted_at IS NULL) as "question_count!: i64",
+                   (SELECT COUNT(*) FROM game_tags gt
+                    JOIN games g ON g.game_id = gt.game_id
+                    WHERE gt.tag_id = t.tag_id AND g.deleted_at IS NULL) as "game_count!: i64"
+            FROM tags t
+            ORDER BY t.name
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_question_tags(&self, question_id: i32) -> Result<Vec<String>> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT t.name
+            FROM tags t
+            JOIN question_tags qt ON qt.tag_id = t.tag_id
+            WHERE qt.question_id = ?
+            ORDER BY t.name
+            "#,
+            question_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn set_question_tags(&self, question_id: i32, tags: &[String]) -> Result<()> {
+        self.get_question(question_id).await?;
+        let tags = normalize_tags(tags)?;
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM question_tags WHERE question_id = ?",
+            question_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        Self::link_question_tags(&mut tx, question_id, &tags).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_game_tags(&self, game_id: i32) -> Result<Vec<String>> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT t.name
+            FROM tags t
+            JOIN game_tags gt ON gt.tag_id = t.tag_id
+            WHERE gt.game_id = ?
+            ORDER BY t.name
+            "#,
+            game_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn set_game_tags(&self, game_id: i32, tags: &[String]) -> Result<()> {
+        self.get_game(game_id).await?;
+        let tags = normalize_tags(tags)?;
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!("DELETE FROM game_tags WHERE game_id = ?", game_id)
+            .execute(&mut *tx)
+            .await?;
+        Self::link_game_tags(&mut tx, game_id, &tags).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_questions_by_tag(&self, tag: &str) -> Result<Vec<Question>> {
+        let tag = normalize_tag(tag);
+        sqlx::query_as!(
+            Question,
+            r#"
+            SELECT q.question_id, q.question_text, q.correct_answer as "correct_answer: Answer",
+                   q.kind as "kind: QuestionKind", q.partial_credit as "partial_credit: bool",
+                   q.difficulty as "difficulty: Difficulty",
+                   q.time_limit_secs, q.explanation, q.source
+            FROM questions q
+            JOIN question_tags qt ON qt.question_id = q.question_id
+            JOIN tags t ON t.tag_id = qt.tag_id
+            WHERE t.name = ? AND q.deleted_at IS NULL
+            ORDER BY q.question_id
+            "#,
+            tag
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_games_by_tag(&self, tag: &str) -> Result<Vec<Game>> {
+        let tag = normalize_tag(tag);
+        sqlx::query_as!(
+            Game,
+            r#"
+            SELECT g.game_id, g.title, g.description,
+                   g.play_order as "play_order: PlayOrder", g.draw_count
+            FROM games g
+            JOIN game_tags gt ON gt.game_id = g.game_id
+            JOIN tags t ON t.tag_id = gt.tag_id
+            WHERE t.name = ? AND g.deleted_at IS NULL
+            ORDER BY g.game_id
+            "#,
+            tag
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn login_admin(&self, username: &str, password: &str) -> Result<Admin> {
+        let admin = sqlx::query_as!(
+            Admin,
+            r#"
+            SELECT admin_id, username, password_hash, created_at
+            FROM admins
+            WHERE username = ?
+            "#,
+            username
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(TriviaError::Unauthorized)?;
+
+        match verify_password(password, &admin.password_hash) {
+            PasswordCheck::Valid => Ok(admin),
+            PasswordCheck::ValidLegacy => {
+                let password_hash = hash_password(password)?;
+                sqlx::query!(
+                    r#"
+                    UPDATE admins
+                    SET password_hash = ?
+                    WHERE admin_id = ?
+                    "#,
+                    password_hash,
+                    admin.admin_id
+                )
+                .execute(&self.pool)
+                .await?;
+
+                Ok(Admin {
+                    password_hash,
+                    ..admin
+                })
+            }
+            PasswordCheck::Invalid => Err(TriviaError::Unauthorized),
+        }
+    }
+
+    async fn login_player(&self, username: &str, password: &str) -> Result<Player> {
+        let player = sqlx::query_as!(
+            Player,
+            r#"
+            SELECT player_id, username, password_hash, created_at
+            FROM players
+            WHERE username = ? AND deleted_at IS NULL
+            "#,
+            username
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(TriviaError::Unauthorized)?;
+
+        match verify_password(password, &player.password_hash) {
+            PasswordCheck::Valid => Ok(player),
+            PasswordCheck::ValidLegacy => {
+                let password_hash = hash_password(password)?;
+                sqlx::query!(
+                    r#"
+                    UPDATE players
+                    SET password_hash = ?
+                    WHERE player_id = ?
+                    "#,
+                    password_hash,
+                    player.player_id
+                )
+                .execute(&self.pool)
+                .await?;
+
+                Ok(Player {
+                    password_hash,
+                    ..player
+                })
+            }
+            PasswordCheck::Invalid => Err(TriviaError::Unauthorized),
+        }
+    }
+
+    async fn get_all_players(&self) -> Result<Vec<Player>> {
+        sqlx::query_as!(
+            Player,
+            "SELECT player_id, username, password_hash, created_at FROM players WHERE deleted_at IS NULL"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn delete_player(&self, player_id: i32) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE players
+            SET deleted_at = CURRENT_TIMESTAMP
+            WHERE player_id = ? AND deleted_at IS NULL
+            "#,
+            player_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Player {}", player_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn create_new_player(&self, username: &str, password: &str) -> Result<()> {
+        let password_hash = hash_password(password)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO players (username, password_hash)
+            VALUES (?, ?)
+            "#,
+            username,
+            password_hash
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::DuplicateUsername(username.to_string())
+            } else {
+                e.into()
+            }
+        })?;
+
+        Ok(())
+    }
+
+    async fn get_game_questions(&self, game_id: i32) -> Result<Vec<GameQuestionFull>> {
+        sqlx::query_as!(
+            GameQuestionFull,
+            r#"
+            SELECT q.question_id, q.question_text, q.correct_answer as "correct_answer: Answer",
+                   q.kind as "kind: QuestionKind", q.partial_credit as "partial_credit: bool",
+                   q.difficulty as "difficulty: Difficulty",
+                   q.time_limit_secs, q.explanation, q.source, gq.question_order
+            FROM questions q
+            JOIN game_questions gq ON q.question_id = gq.question_id
+            WHERE gq.game_id = ? AND q.deleted_at IS NULL
+            ORDER BY gq.question_order
+            "#,
+            game_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_all_questions(&self) -> Result<Vec<Question>> {
+        sqlx::query_as!(
+            Question,
+            r#"
+            SELECT question_id, question_text, correct_answer as "correct_answer: Answer",
+                   kind as "kind: QuestionKind", partial_credit as "partial_credit: bool",
+                   difficulty as "difficulty: Difficulty",
+                   time_limit_secs, explanation, source
+            FROM questions
+            WHERE deleted_at IS NULL
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_question_usage(&self) -> Result<Vec<QuestionUsage>> {
+        sqlx::query_as!(
+            QuestionUsage,
+            r#"
+            SELECT q.question_id, q.question_text, q.kind as "kind: QuestionKind",
+                   q.difficulty as "difficulty: Difficulty",
+                   (SELECT COUNT(*) FROM game_questions gq
+                    JOIN games g ON g.game_id = gq.game_id
+                    WHERE gq.question_id = q.question_id AND g.deleted_at IS NULL) as "game_count!: i64",
+                   (SELECT COUNT(*) FROM session_answers sa
+                    WHERE sa.question_id = q.question_id) as "answer_count!: i64"
+            FROM questions q
+            WHERE q.deleted_at IS NULL
+            ORDER BY q.question_id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_games_using_question(&self, question_id: i32) -> Result<Vec<Game>> {
+        sqlx::query_as!(
+            Game,
+            r#"
+            SELECT g.game_id, g.title, g.description,
+                   g.play_order as "play_order: PlayOrder", g.draw_count
+            FROM games g
+            JOIN game_questions gq ON g.game_id = gq.game_id
+            WHERE gq.question_id = ? AND g.deleted_at IS NULL
+            ORDER BY g.title
+            "#,
+            question_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn search_questions(&self, search: &QuestionSearch, limit: i32) -> Result<Vec<Question>> {
+        // Every word is required (+) and matches by prefix (*). An empty
+        // pattern means no keyword filter.
+        let pattern = search_terms(search)?
+            .map(|terms| {
+                terms
+                    .iter()
+                    .map(|t| format!("+{}*", t))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+        let tag = search.tag.as_deref().map(normalize_tag);
+        let kind = search.kind.map(|k| k.as_str());
+
+        sqlx::query_as!(
+            Question,
+            r#"
+            SELECT q.question_id, q.question_text, q.correct_answer as "correct_answer: Answer",
+                   q.kind as "kind: QuestionKind", q.partial_credit as "partial_credit: bool",
+                   q.difficulty as "difficulty: Difficulty",
+                   q.time_limit_secs, q.explanation, q.source
+            FROM questions q
+            LEFT JOIN admins a ON a.admin_id = q.created_by
+            WHERE q.deleted_at IS NULL
+              AND (? = '' OR MATCH (q.question_text, q.explanation) AGAINST (? IN BOOLEAN MODE))
+              AND (? IS NULL OR EXISTS (
+                    SELECT 1 FROM question_tags qt
+                    JOIN tags t ON t.tag_id = qt.tag_id
+                    WHERE qt.question_id = q.question_id AND t.name = ?))
+              AND (? IS NULL OR a.username = ?)
+              AND (? IS NULL OR q.kind = ?)
+              AND (? IS NULL OR DATE(q.created_at) >= ?)
+              AND (? IS NULL OR DATE(q.created_at) <= ?)
+            ORDER BY MATCH (q.question_text, q.explanation) AGAINST (? IN BOOLEAN MODE) DESC,
+                     q.question_id
+            LIMIT ?
+            "#,
+            pattern,
+            pattern,
+            tag,
+            tag,
+            search.author,
+            search.author,
+            kind,
+            kind,
+            search.created_from,
+            search.created_from,
+            search.created_to,
+            search.created_to,
+            pattern,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn add_question_to_game(&self, game_id: i32, question_id: i32, order: i32) -> Result<()> {
+        self.get_game(game_id).await?;
+        self.get_question(question_id).await?;
+
+        sqlx::query!(
+            r#"
+                INSERT INTO game_questions (game_id, question_id, question_order)
+                VALUES (?, ?, ?)
+                "#,
+            game_id,
+            question_id,
+            order
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::InvalidInput(format!(
+                    "Question {} is already in this game",
+                    question_id
+                ))
+            } else if is_foreign_key_violation(&e) {
+                TriviaError::NotFound(format!("Game {} or question {}", game_id, question_id))
+            } else {
+                e.into()
+            }
+        })?;
+
+        Ok(())
+    }
+
+    async fn set_question_explanation(
+        &self,
+        question_id: i32,
+        explanation: Option<&str>,
+        source: Option<&str>,
+    ) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE questions
+            SET explanation = ?, source = ?
+            WHERE question_id = ? AND deleted_at IS NULL
+            "#,
+            explanation,
+            source,
+            question_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn set_question_difficulty(
+        &self,
+        question_id: i32,
+        difficulty: Difficulty,
+    ) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE questions
+            SET difficulty = ?
+            WHERE question_id = ? AND deleted_at IS NULL
+            "#,
+            difficulty.as_str(),
+            question_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn set_question_time_limit(&self, question_id: i32, seconds: Option<i32>) -> Result<()> {
+        validate_time_limit(seconds)?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE questions
+            SET time_limit_secs = ?
+            WHERE question_id = ? AND deleted_at IS NULL
+            "#,
+            seconds,
+            question_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_question(&self, question_id: i32) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE questions
+            SET deleted_at = CURRENT_TIMESTAMP
+            WHERE question_id = ? AND deleted_at IS NULL
+            "#,
+            question_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_game_question(&self, game_id: i32, question_id: i32) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM game_questions
+            WHERE game_id = ? AND question_id = ?
+            "#,
+            game_id,
+            question_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!(
+                "Question {} in game {}",
+                question_id, game_id
+            )));
+        }
+
+        self.renumber_game_questions(game_id).await?;
+
+        Ok(())
+    }
+
+    async fn update_question_order(
+        &self,
+        game_id: i32,
+        question_id: i32,
+        order: i32,
+    ) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE game_questions
+            SET question_order = ?
+            WHERE game_id = ? AND question_id = ?
+              AND game_id IN (SELECT game_id FROM games WHERE deleted_at IS NULL)
+              AND question_id IN (SELECT question_id FROM questions WHERE deleted_at IS NULL)
+            "#,
+            order,
+            game_id,
+            question_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!(
+                "Question {} in game {}",
+                question_id, game_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn reorder_game_questions(&self, game_id: i32, question_ids: &[i32]) -> Result<()> {
+        check_question_order(&self.get_game_questions(game_id).await?, question_ids)?;
+        let mut tx = self.pool.begin().await?;
+
+        for (new_order, question_id) in question_ids.iter().enumerate() {
+            sqlx::query!(
+                r#"
+                UPDATE game_questions
+                SET question_order = ?
+                WHERE game_id = ? AND question_id = ?
+                "#,
+                new_order as i32 + 1,
+                game_id,
+                question_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_max_question_order(&self, game_id: i32) -> Result<i32> {
+        let result = sqlx::query!(
+            r#"
+                SELECT COALESCE(MAX(question_order), 0) as max_order
+                FROM game_questions
+                WHERE game_id = ?
+                "#,
+            game_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result.max_order.unwrap_or(0))
+    }
+
+    async fn create_question_with_details(
+        &self,
+        question: &NewQuestion,
+        admin_id: i32,
+    ) -> Result<i32> {
+        let tags = validate_new_question(question)?;
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO questions
+                (question_text, correct_answer, kind, partial_credit, difficulty,
+                 time_limit_secs, explanation, source, created_by)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            question.text.trim(),
+            question.answer.correct_answer(),
+            question.answer.kind().as_str(),
+            question.answer.partial_credit(),
+            question.difficulty.as_str(),
+            question.time_limit_secs,
+            question.explanation.as_deref(),
+            question.source.as_deref(),
+            admin_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let question_id = result.last_insert_id() as i32;
+
+        Self::insert_answer_rows(&mut tx, question_id, &question.answer).await?;
+        Self::link_question_tags(&mut tx, question_id, &tags).await?;
+
+        tx.commit().await?;
+        Ok(question_id)
+    }
+
+    async fn create_question(&self, text: &str, answer: bool, admin_id: i32) -> Result<i32> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO questions (question_text, correct_answer, created_by)
+            VALUES (?, ?, ?)
+            "#,
+            text,
+            answer,
+            admin_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_id() as i32)
+    }
+
+    async fn create_multiple_choice_question(
+        &self,
+        text: &str,
+        choices: &[String],
+        correct_choice: usize,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_choices(choices, correct_choice)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO questions (question_text, correct_answer, kind, created_by)
+            VALUES (?, FALSE, ?, ?)
+            "#,
+            text,
+            QuestionKind::MultipleChoice.as_str(),
+            admin_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let question_id = result.last_insert_id() as i32;
+
+        let answer = AnswerSpec::MultipleChoice {
+            choices: choices.to_vec(),
+            correct_choice,
+        };
+        Self::insert_answer_rows(&mut tx, question_id, &answer).await?;
+
+        tx.commit().await?;
+        Ok(question_id)
+    }
+
+    async fn get_question_choices(&self, question_id: i32) -> Result<Vec<QuestionChoice>> {
+        sqlx::query_as!(
+            QuestionChoice,
+            r#"
+            SELECT choice_id, question_id, choice_order, choice_text,
+                   is_correct as "is_correct: bool"
+            FROM question_choices
+            WHERE question_id = ?
+            ORDER BY choice_order
+            "#,
+            question_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn create_free_text_question(
+        &self,
+        text: &str,
+        accepted: &[String],
+        max_distance: i32,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_accepted_answers(accepted, max_distance)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO questions (question_text, correct_answer, kind, created_by)
+            VALUES (?, FALSE, ?, ?)
+            "#,
+            text,
+            QuestionKind::FreeText.as_str(),
+            admin_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let question_id = result.last_insert_id() as i32;
+
+        let answer = AnswerSpec::FreeText {
+            accepted: accepted.to_vec(),
+            max_distance,
+        };
+        Self::insert_answer_rows(&mut tx, question_id, &answer).await?;
+
+        tx.commit().await?;
+        Ok(question_id)
+    }
+
+    async fn get_accepted_answers(&self, question_id: i32) -> Result<Vec<AcceptedAnswer>> {
+        sqlx::query_as!(
+            AcceptedAnswer,
+            r#"
+            SELECT accepted_answer_id, question_id, answer_text, max_distance
+            FROM accepted_answers
+            WHERE question_id = ?
+            ORDER BY accepted_answer_id
+            "#,
+            question_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn create_numeric_question(
+        &self,
+        text: &str,
+        target_value: f64,
+        tolerance: f64,
+        tolerance_percent: bool,
+        unit: Option<&str>,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_numeric_answer(target_value, tolerance, unit)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO questions (question_text, correct_answer, kind, created_by)
+            VALUES (?, FALSE, ?, ?)
+            "#,
+            text,
+            QuestionKind::Numeric.as_str(),
+            admin_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let question_id = result.last_insert_id() as i32;
+
+        let answer = AnswerSpec::Numeric {
+            target_value,
+            tolerance,
+            tolerance_percent,
+            unit: unit.map(str::to_string),
+        };
+        Self::insert_answer_rows(&mut tx, question_id, &answer).await?;
+
+        tx.commit().await?;
+        Ok(question_id)
+    }
+
+    async fn get_numeric_answer(&self, question_id: i32) -> Result<NumericAnswer> {
+        sqlx::query_as!(
+            NumericAnswer,
+            r#"
+            SELECT question_id, target_value, tolerance,
+                   tolerance_percent as "tolerance_percent: bool", unit
+            FROM numeric_answers
+            WHERE question_id = ?
+            "#,
+            question_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| TriviaError::NotFound(format!("Answer for question {}", question_id)))
+    }
+
+    async fn create_ordering_question(
+        &self,
+        text: &str,
+        items: &[String],
+        partial_credit: bool,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_ordering_items(items)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO questions (question_text, correct_answer, kind, partial_credit, created_by)
+            VALUES (?, FALSE, ?, ?, ?)
+            "#,
+            text,
+            QuestionKind::Ordering.as_str(),
+            partial_credit,
+            admin_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let question_id = result.last_insert_id() as i32;
+
+        let answer = AnswerSpec::Ordering {
+            items: items.to_vec(),
+            partial_credit,
+        };
+        Self::insert_answer_rows(&mut tx, question_id, &answer).await?;
+
+        tx.commit().await?;
+        Ok(question_id)
+    }
+
+    async fn get_ordering_items(&self, question_id: i32) -> Result<Vec<OrderingItem>> {
+        sqlx::query_as!(
+            OrderingItem,
+            r#"
+            SELECT item_id, question_id, item_order, item_text
+            FROM ordering_items
+            WHERE question_id = ?
+            ORDER BY item_order
+            "#,
+            question_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_question(&self, question_id: i32) -> Result<Question> {
+        sqlx::query_as!(
+            Question,
+            r#"
+            SELECT question_id, question_text, correct_answer as "correct_answer: Answer",
+                   kind as "kind: QuestionKind", partial_credit as "partial_credit: bool",
+                   difficulty as "difficulty: Difficulty",
+                   time_limit_secs, explanation, source
+            FROM questions
+            WHERE question_id = ? AND deleted_at IS NULL
+            "#,
+            question_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| TriviaError::NotFound(format!("Question {}", question_id)))
+    }
+
+    async fn update_question(
+        &self,
+        question_id: i32,
+        text: &str,
+        answer: &AnswerSpec,
+        explanation: Option<&str>,
+        source: Option<&str>,
+        admin_id: i32,
+    ) -> Result<()> {
+        let current = self.get_question(question_id).await?;
+        let current_answer = self.get_answer_spec(&current).await?;
+        if !check_edit(&current, &current_answer, text, answer, explanation, source)? {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO question_revisions
+                (question_id, question_text, answer, explanation, source, edited_by)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            question_id,
+            current.question_text,
+            current_answer.to_json()?,
+            current.explanation,
+            current.source,
+            admin_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE questions
+            SET question_text = ?, correct_answer = ?, partial_credit = ?,
+                explanation = ?, source = ?
+            WHERE question_id = ?
+            "#,
+            text,
+            answer.correct_answer(),
+            answer.partial_credit(),
+            explanation,
+            source,
+            question_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Self::delete_answer_rows(&mut tx, question_id).await?;
+        Self::insert_answer_rows(&mut tx, question_id, answer).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_question_revisions(&self, question_id: i32) -> Result<Vec<QuestionRevision>> {
+        sqlx::query_as!(
+            QuestionRevision,
+            r#"
+            SELECT r.revision_id, r.question_id, r.question_text, r.answer,
+                   r.explanation, r.source, r.edited_by, a.username as "editor?", r.edited_at
+            FROM question_revisions r
+            LEFT JOIN admins a ON a.admin_id = r.edited_by
+            WHERE r.question_id = ?
+            ORDER BY r.revision_id DESC
+            "#,
+            question_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn create_game(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        admin_id: i32,
+    ) -> Result<i32> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO games (title, description, created_by)
+            VALUES (?, ?, ?)
+            "#,
+            title,
+            description,
+            admin_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::DuplicateTitle(title.to_string())
+            } else {
+                e.into()
+            }
+        })?;
+
+        Ok(result.last_insert_id() as i32)
+    }
+
+    async fn create_game_with_tags(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        tags: &[String],
+        admin_id: i32,
+    ) -> Result<i32> {
+        let tags = normalize_tags(tags)?;
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO games (title, description, created_by)
+            VALUES (?, ?, ?)
+            "#,
+            title,
+            description,
+            admin_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::DuplicateTitle(title.to_string())
+            } else {
+                e.into()
+            }
+        })?;
+        let game_id = result.last_insert_id() as i32;
+
+        Self::link_game_tags(&mut tx, game_id, &tags).await?;
+
+        tx.commit().await?;
+        Ok(game_id)
+    }
+
+    async fn get_game(&self, game_id: i32) -> Result<Game> {
+        sqlx::query_as!(
+            Game,
+            r#"
+            SELECT game_id, title, description,
+                   play_order as "play_order: PlayOrder", draw_count
+            FROM games
+            WHERE game_id = ? AND deleted_at IS NULL
+            "#,
+            game_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| TriviaError::NotFound(format!("Game {}", game_id)))
+    }
+
+    async fn update_game(
+        &self,
+        game_id: i32,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE games
+            SET title = ?, description = ?
+            WHERE game_id = ? AND deleted_at IS NULL
+            "#,
+            title,
+            description,
+            game_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::DuplicateTitle(title.to_string())
+            } else {
+                e.into()
+            }
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn update_game_with_tags(
+        &self,
+        game_id: i32,
+        title: &str,
+        description: Option<&str>,
+        tags: Option<&[String]>,
+    ) -> Result<()> {
+        let tags = tags.map(normalize_tags).transpose()?;
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE games
+            SET title = ?, description = ?
+            WHERE game_id = ? AND deleted_at IS NULL
+            "#,
+            title,
+            description,
+            game_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::DuplicateTitle(title.to_string())
+            } else {
+                e.into()
+            }
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        if let Some(tags) = tags {
+            sqlx::query!("DELETE FROM game_tags WHERE game_id = ?", game_id)
+                .execute(&mut *tx)
+                .await?;
+            Self::link_game_tags(&mut tx, game_id, &tags).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_game_usage(&self, game_id: i32) -> Result<GameUsage> {
+        sqlx::query_as!(
+            GameUsage,
+            r#"
+            SELECT
+                (SELECT COUNT(*)
+                 FROM game_questions gq
+                 JOIN questions q ON q.question_id = gq.question_id
+                 WHERE gq.game_id = ? AND q.deleted_at IS NULL) as "question_count!: i64",
+                (SELECT COUNT(*) FROM game_sessions WHERE game_id = ?) as "session_count!: i64"
+            "#,
+            game_id,
+            game_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn set_game_play_order(
+        &self,
+        game_id: i32,
+        play_order: PlayOrder,
+        draw_count: Option<i32>,
+    ) -> Result<()> {
+        validate_play_order(play_order, draw_count)?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE games
+            SET play_order = ?, draw_count = ?
+            WHERE game_id = ? AND deleted_at IS NULL
+            "#,
+            play_order.as_str(),
+            draw_count,
+            game_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_game(&self, game_id: i32) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE games
+            SET deleted_at = CURRENT_TIMESTAMP
+            WHERE game_id = ? AND deleted_at IS NULL
+            "#,
+            game_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn start_session(
+        &self,
+        player_id: i32,
+        game_id: i32,
+        mode: GameMode,
+        seed: Option<i64>,
+    ) -> Result<i32> {
+        let game = self.get_game(game_id).await?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO game_sessions (player_id, game_id, mode, seed, play_order, draw_count)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            player_id,
+            game_id,
+            mode.as_str(),
+            seed,
+            game.play_order.as_str(),
+            game.draw_count
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_id() as i32)
+    }
+
+    async fn record_answer(
+        &self,
+        session_id: i32,
+        question_id: i32,
+        submitted_answer: &str,
+        is_correct: bool,
+        score: f64,
+        response_time_ms: i32,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO session_answers
+                (session_id, question_id, submitted_answer, is_correct, score, response_time_ms)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            session_id,
+            question_id,
+            submitted_answer,
+            is_correct,
+            score,
+            response_time_ms
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn finish_session(
+        &self,
+        session_id: i32,
+        correct_answers: i32,
+        total_questions: i32,
+        score: f64,
+        max_score: f64,
+        bonus: f64,
+    ) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE game_sessions
+            SET ended_at = CURRENT_TIMESTAMP, correct_answers = ?, total_questions = ?, score = ?,
+                max_score = ?, bonus = ?
+            WHERE session_id = ?
+            "#,
+            correct_answers,
+            total_questions,
+            score,
+            max_score,
+            bonus,
+            session_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Session {}", session_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn get_session(&self, session_id: i32) -> Result<GameSession> {
+        sqlx::query_as!(
+            GameSession,
+            r#"
+            SELECT session_id, player_id, game_id, mode as "mode: GameMode", seed,
+                   play_order as "play_order: PlayOrder", draw_count, started_at, ended_at,
+                   correct_answers, total_questions, score, max_score, bonus
+            FROM game_sessions
+            WHERE session_id = ?
+            "#,
+            session_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| TriviaError::NotFound(format!("Session {}", session_id)))
+    }
+
+    async fn get_player_sessions(&self, player_id: i32) -> Result<Vec<GameSession>> {
+        sqlx::query_as!(
+            GameSession,
+            r#"
+            SELECT session_id, player_id, game_id, mode as "mode: GameMode", seed,
+                   play_order as "play_order: PlayOrder", draw_count, started_at, ended_at,
+                   correct_answers, total_questions, score, max_score, bonus
+            FROM game_sessions
+            WHERE player_id = ?
+            ORDER BY started_at DESC, session_id DESC
+            "#,
+            player_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_session_answers(&self, session_id: i32) -> Result<Vec<SessionAnswer>> {
+        sqlx::query_as!(
+            SessionAnswer,
+            r#"
+            SELECT answer_id, session_id, question_id, submitted_answer,
+                   is_correct as "is_correct: bool", score, response_time_ms, answered_at
+            FROM session_answers
+            WHERE session_id = ?
+            ORDER BY answer_id
+            "#,
+            session_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_game_leaderboard(
+        &self,
+        game_id: i32,
+        mode: GameMode,
+        basis: LeaderboardBasis,
+        limit: i32,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let latest = basis == LeaderboardBasis::Latest;
+
+        sqlx::query_as!(
+            LeaderboardEntry,
+            r#"
+            SELECT
+                ROW_NUMBER() OVER (
+                    ORDER BY chosen.score + chosen.bonus DESC, chosen.time_ms ASC, chosen.ended_at ASC
+                ) AS "rank!: i64",
+                chosen.player_id AS "player_id!: i32",
+                chosen.username AS "username!: String",
+                chosen.correct_answers AS "correct_answers!: i64",
+                chosen.total_questions AS "total_questions!: i64",
+                chosen.score AS "score!: f64",
+                chosen.max_score AS "max_score!: f64",
+                chosen.bonus AS "bonus!: f64",
+                chosen.time_ms AS "time_ms!: i64",
+                1 AS "games_played!: i64"
+            FROM (
+                SELECT s.player_id, p.username, s.correct_answers, s.total_questions,
+                       s.score, s.max_score, s.bonus, s.ended_at, COALESCE(t.time_ms, 0) AS time_ms,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY s.player_id
+                           ORDER BY CASE WHEN ? THEN s.ended_at END DESC,
+                                    s.score + s.bonus DESC,
+                                    COALESCE(t.time_ms, 0) ASC,
+                                    s.ended_at ASC
+                       ) AS attempt
+                FROM game_sessions s
+                JOIN players p ON p.player_id = s.player_id
+                LEFT JOIN (
+                    SELECT session_id, CAST(SUM(response_time_ms) AS SIGNED) AS time_ms
+                    FROM session_answers
+                    GROUP BY session_id
+                ) t ON t.session_id = s.session_id
+                WHERE s.game_id = ? AND s.mode = ? AND s.ended_at IS NOT NULL
+                  AND p.deleted_at IS NULL
+            ) chosen
+            WHERE chosen.attempt = 1
+            ORDER BY chosen.score + chosen.bonus DESC, chosen.time_ms ASC, chosen.ended_at ASC
+            LIMIT ?
+            "#,
+            latest,
+            game_id,
+            mode.as_str(),
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_global_leaderboard(
+        &self,
+        mode: GameMode,
+        basis: LeaderboardBasis,
+        limit: i32,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let latest = basis == LeaderboardBasis::Latest;
+
+        sqlx::query_as!(
+            LeaderboardEntry,
+            r#"
+            SELECT
+                ROW_NUMBER() OVER (
+                    ORDER BY SUM(chosen.score + chosen.bonus) DESC,
+                             SUM(chosen.time_ms) ASC,
+                             MAX(chosen.ended_at) ASC
+                ) AS "rank!: i64",
+                chosen.player_id AS "player_id!: i32",
+                chosen.username AS "username!: String",
+                CAST(SUM(chosen.correct_answers) AS SIGNED) AS "correct_answers!: i64",
+                CAST(SUM(chosen.total_questions) AS SIGNED) AS "total_questions!: i64",
+                SUM(chosen.score) AS "score!: f64",
+                SUM(chosen.max_score) AS "max_score!: f64",
+                SUM(chosen.bonus) AS "bonus!: f64",
+                CAST(SUM(chosen.time_ms) AS SIGNED) AS "time_ms!: i64",
+                COUNT(*) AS "games_played!: i64"
+            FROM (
+                SELECT s.player_id, p.username, s.correct_answers, s.total_questions,
+                       s.score, s.max_score, s.bonus, s.ended_at, COALESCE(t.time_ms, 0) AS time_ms,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY s.player_id, s.game_id
+                           ORDER BY CASE WHEN ? THEN s.ended_at END DESC,
+                                    s.score + s.bonus DESC,
+                                    COALESCE(t.time_ms, 0) ASC,
+                                    s.ended_at ASC
+                       ) AS attempt
+                FROM game_sessions s
+                JOIN players p ON p.player_id = s.player_id
+                JOIN games g ON g.game_id = s.game_id
+                LEFT JOIN (
+                    SELECT session_id, CAST(SUM(response_time_ms) AS SIGNED) AS time_ms
+                    FROM session_answers
+                    GROUP BY session_id
+                ) t ON t.session_id = s.session_id
+                WHERE s.mode = ? AND s.ended_at IS NOT NULL
+                  AND p.deleted_at IS NULL AND g.deleted_at IS NULL
+            ) chosen
+            WHERE chosen.attempt = 1
+            GROUP BY chosen.player_id, chosen.username
+            ORDER BY SUM(chosen.score + chosen.bonus) DESC,
+                     SUM(chosen.time_ms) ASC,
+                     MAX(chosen.ended_at) ASC
+            LIMIT ?
+            "#,
+            latest,
+            mode.as_str(),
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn record_survival_run(
+        &self,
+        player_id: i32,
+        game_id: Option<i32>,
+        lives: i32,
+        questions_answered: i32,
+        correct_answers: i32,
+        score: f64,
+    ) -> Result<i32> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO survival_runs
+                (player_id, game_id, lives, questions_answered, correct_answers, score)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            player_id,
+            game_id,
+            lives,
+            questions_answered,
+            correct_answers,
+            score
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_id() as i32)
+    }
+
+    async fn get_survival_high_scores(
+        &self,
+        game_id: Option<i32>,
+        lives: i32,
+        limit: i32,
+    ) -> Result<Vec<SurvivalScore>> {
+        sqlx::query_as!(
+            SurvivalScore,
+            r#"
+            SELECT
+                ROW_NUMBER() OVER (
+                    ORDER BY best.correct_answers DESC, best.score DESC, best.played_at ASC
+                ) AS "rank!: i64",
+                best.player_id,
+                best.username,
+                best.correct_answers,
+                best.questions_answered,
+                best.score,
+                best.played_at
+            FROM (
+                SELECT r.player_id, p.username, r.correct_answers, r.questions_answered,
+                       r.score, r.played_at,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY r.player_id
+                           ORDER BY r.correct_answers DESC, r.score DESC, r.played_at ASC
+                       ) AS attempt
+                FROM survival_runs r
+                JOIN players p ON p.player_id = r.player_id
+                LEFT JOIN games g ON g.game_id = r.game_id
+                WHERE r.game_id <=> ? AND r.lives = ?
+                  AND p.deleted_at IS NULL AND g.deleted_at IS NULL
+            ) best
+            WHERE best.attempt = 1
+            ORDER BY best.correct_answers DESC, best.score DESC, best.played_at ASC
+            LIMIT ?
+            "#,
+            game_id,
+            lives,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_practice_questions(
+        &self,
+        player_id: i32,
+        now: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<GameQuestionFull>> {
+        sqlx::query_as!(
+            GameQuestionFull,
+            r#"
+            SELECT q.question_id, q.question_text, q.correct_answer as "correct_answer: Answer",
+                   q.kind as "kind: QuestionKind", q.partial_credit as "partial_credit: bool",
+                   q.difficulty as "difficulty: Difficulty",
+                   q.time_limit_secs, q.explanation, q.source,
+                   CAST(ROW_NUMBER() OVER (
+                       ORDER BY queue.waiting_since, q.question_id
+                   ) AS SIGNED) as "question_order!: i32"
+            FROM (
+                SELECT question_id, waiting_since
+                FROM (
+                    SELECT pc.question_id,
+                           CASE
+                               WHEN pc.due_at <= ? THEN pc.due_at
+                               -- Missed in a game since it was last practiced.
+                               ELSE (
+                                   SELECT MIN(sa.answered_at)
+                                   FROM session_answers sa
+                                   JOIN game_sessions s ON s.session_id = sa.session_id
+                                   WHERE s.player_id = pc.player_id
+                                     AND sa.question_id = pc.question_id
+                                     AND sa.is_correct = FALSE
+                                     AND sa.answered_at > pc.reviewed_at
+                               )
+                           END AS waiting_since
+                    FROM practice_cards pc
+                    WHERE pc.player_id = ?
+                ) cards
+                WHERE waiting_since IS NOT NULL
+                UNION ALL
+                SELECT sa.question_id, MIN(sa.answered_at) AS waiting_since
+                FROM session_answers sa
+                JOIN game_sessions s ON s.session_id = sa.session_id
+                WHERE s.player_id = ? AND sa.is_correct = FALSE
+                  AND NOT EXISTS (
+                      SELECT 1 FROM practice_cards pc
+                      WHERE pc.player_id = s.player_id AND pc.question_id = sa.question_id
+                  )
+                GROUP BY sa.question_id
+            ) queue
+            JOIN questions q ON q.question_id = queue.question_id
+            WHERE q.deleted_at IS NULL
+            ORDER BY queue.waiting_since, q.question_id
+            LIMIT ?
+            "#,
+            now,
+            player_id,
+            player_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_practice_card(
+        &self,
+        player_id: i32,
+        question_id: i32,
+    ) -> Result<Option<PracticeCard>> {
+        sqlx::query_as!(
+            PracticeCard,
+            r#"
+            SELECT player_id, question_id, repetitions, ease_factor, interval_days,
+                   due_at, reviewed_at
+            FROM practice_cards
+            WHERE player_id = ? AND question_id = ?
+            "#,
+            player_id,
+            question_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn save_practice_card(&self, card: &PracticeCard) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO practice_cards
+                (player_id, question_id, repetitions, ease_factor, interval_days,
+                 due_at, reviewed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                repetitions = VALUES(repetitions),
+                ease_factor = VALUES(ease_factor),
+                interval_days = VALUES(interval_days),
+                due_at = VALUES(due_at),
+                reviewed_at = VALUES(reviewed_at)
+            "#,
+            card.player_id,
+            card.question_id,
+            card.repetitions,
+            card.ease_factor,
+            card.interval_days,
+            card.due_at,
+            card.reviewed_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_next_practice_due(&self, player_id: i32) -> Result<Option<DateTime<Utc>>> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT MIN(pc.due_at) as "due_at: DateTime<Utc>"
+            FROM practice_cards pc
+            JOIN questions q ON q.question_id = pc.question_id
+            WHERE pc.player_id = ? AND q.deleted_at IS NULL
+            "#,
+            player_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+}
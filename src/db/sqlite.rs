@@ -0,0 +1,2249 @@
+use super::{
+    check_edit, check_question_order, migrations, normalize_tags, search_terms,
+    validate_accepted_answers, validate_choices, validate_new_question, validate_numeric_answer,
+    validate_ordering_items, validate_play_order, validate_time_limit, MigrationStatus,
+    TriviaStore,
+};
+use crate::error::{is_foreign_key_violation, is_unique_violation, Result, TriviaError};
+use crate::models::{
+    normalize_tag, AcceptedAnswer, Admin, AnswerSpec, Difficulty, Game, GameMode, GameQuestionFull,
+    GameSession, GameUsage, LeaderboardBasis, LeaderboardEntry, NewQuestion, NumericAnswer,
+    OrderingItem, PlayOrder, Player, PracticeCard, Question, QuestionChoice, QuestionKind,
+    QuestionRevision, QuestionSearch, QuestionUsage, SessionAnswer, SurvivalScore, Tag, TrashItem,
+    TrashKind,
+};
+use crate::password::{hash_password, verify_password, PasswordCheck};
+use chrono::{DateTime, Utc};
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::{Sqlite, SqlitePool};
+use sqlx::{QueryBuilder, Transaction};
+
+/// SQLite-backed store for running without a MariaDB server.
+///
+/// SQLite can't share the compile-time checked `query!` macros with the MySQL
+/// backend, so these queries are checked at runtime instead.
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
+
+pub struct SqliteStore {
+    pool: SqlitePool,
+}
+
+impl SqliteStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Closes any gaps in a game's `question_order`.
+    async fn renumber_game_questions(&self, game_id: i32) -> Result<()> {
+        let questions = self.get_game_questions(game_id).await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        for (new_order, question) in questions.iter().enumerate() {
+            let new_order = (new_order + 1) as i32;
+            if new_order != question.question_order {
+                sqlx::query(
+                    r#"
+                    UPDATE game_questions
+                    SET question_order = ?
+                    WHERE game_id = ? AND question_id = ?
+                    "#,
+                )
+                .bind(new_order)
+                .bind(game_id)
+                .bind(question.question_id)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Inserts the choices, accepted answers, numeric target or items that
+    /// `answer` describes.
+    async fn insert_answer_rows(
+        tx: &mut Transaction<'_, Sqlite>,
+        question_id: i32,
+        answer: &AnswerSpec,
+    ) -> Result<()> {
+        match answer {
+            AnswerSpec::TrueFalse(_) => {}
+            AnswerSpec::MultipleChoice {
+                choices,
+                correct_choice,
+            } => {
+                for (i, choice) in choices.iter().enumerate() {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO question_choices (question_id, choice_order, choice_text, is_correct)
+                        VALUES (?, ?, ?, ?)
+                        "#,
+                    )
+                    .bind(question_id)
+                    .bind(i as i32 + 1)
+                    .bind(choice.trim())
+                    .bind(i == *correct_choice)
+                    .execute(&mut **tx)
+                    .await?;
+                }
+            }
+            AnswerSpec::FreeText {
+                accepted,
+                max_distance,
+            } => {
+                for answer in accepted {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO accepted_answers (question_id, answer_text, max_distance)
+                        VALUES (?, ?, ?)
+                        "#,
+                    )
+                    .bind(question_id)
+                    .bind(answer.trim())
+                    .bind(max_distance)
+                    .execute(&mut **tx)
+                    .await?;
+                }
+            }
+            AnswerSpec::Numeric {
+                target_value,
+                tolerance,
+                tolerance_percent,
+                unit,
+            } => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO numeric_answers
+                        (question_id, target_value, tolerance, tolerance_percent, unit)
+                    VALUES (?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(question_id)
+                .bind(target_value)
+                .bind(tolerance)
+                .bind(tolerance_percent)
+                .bind(unit)
+                .execute(&mut **tx)
+                .await?;
+            }
+            AnswerSpec::Ordering { items, .. } => {
+                for (i, item) in items.iter().enumerate() {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO ordering_items (question_id, item_order, item_text)
+                        VALUES (?, ?, ?)
+                        "#,
+                    )
+                    .bind(question_id)
+                    .bind(i as i32 + 1)
+                    .bind(item.trim())
+                    .execute(&mut **tx)
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes whatever kind-specific answer rows a question has.
+    async fn delete_answer_rows(tx: &mut Transaction<'_, Sqlite>, question_id: i32) -> Result<()> {
+        for table in [
+            "question_choices",
+            "accepted_answers",
+            "numeric_answers",
+            "ordering_items",
+        ] {
+            sqlx::query(&format!("DELETE FROM {} WHERE question_id = ?", table))
+                .bind(question_id)
+                .execute(&mut **tx)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the tags linked to one question or game, creating missing
+    /// tags first.
+    async fn replace_tags(&self, table: &str, key: &str, id: i32, tags: &[String]) -> Result<()> {
+        let tags = normalize_tags(tags)?;
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(&format!("DELETE FROM {} WHERE {} = ?", table, key))
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        Self::link_tags(&mut tx, table, key, id, &tags).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Links already normalized tags to one question or game, creating
+    /// missing tags first.
+    async fn link_tags(
+        tx: &mut Transaction<'_, Sqlite>,
+        table: &str,
+        key: &str,
+        id: i32,
+        tags: &[String],
+    ) -> Result<()> {
+        for tag in tags {
+            sqlx::query("INSERT OR IGNORE INTO tags (name) VALUES (?)")
+                .bind(tag)
+                .execute(&mut **tx)
+                .await?;
+            sqlx::query(&format!(
+                "INSERT INTO {} ({}, tag_id) SELECT ?, tag_id FROM tags WHERE name = ?",
+                table, key
+            ))
+            .bind(id)
+            .bind(tag)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn linked_tags(&self, table: &str, key: &str, id: i32) -> Result<Vec<String>> {
+        sqlx::query_scalar::<_, String>(&format!(
+            r#"
+            SELECT t.name
+            FROM tags t
+            JOIN {} l ON l.tag_id = t.tag_id
+            WHERE l.{} = ?
+            ORDER BY t.name
+            "#,
+            table, key
+        ))
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+}
+
+/// Table and key column behind each kind of trash.
+fn trash_table(kind: TrashKind) -> (&'static str, &'static str) {
+    match kind {
+        TrashKind::Player => ("players", "player_id"),
+        TrashKind::Game => ("games", "game_id"),
+        TrashKind::Question => ("questions", "question_id"),
+    }
+}
+
+impl TriviaStore for SqliteStore {
+    async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        let mut conn = self.pool.acquire().await?;
+        migrations::status(&mut *conn, &MIGRATOR).await
+    }
+
+    async fn migrate_up(&self) -> Result<()> {
+        MIGRATOR.run(&self.pool).await.map_err(sqlx::Error::from)?;
+        Ok(())
+    }
+
+    async fn migrate_down(&self) -> Result<Option<i64>> {
+        let status = self.migration_status().await?;
+        let Some((latest, target)) = migrations::undo_target(&status) else {
+            return Ok(None);
+        };
+
+        MIGRATOR
+            .undo(&self.pool, target)
+            .await
+            .map_err(sqlx::Error::from)?;
+        Ok(Some(latest))
+    }
+
+    async fn get_games(&self) -> Result<Vec<Game>> {
+        sqlx::query_as::<_, Game>(
+            "SELECT game_id, title, description, play_order, draw_count FROM games WHERE deleted_at IS NULL",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_trash(&self) -> Result<Vec<TrashItem>> {
+        sqlx::query_as::<_, TrashItem>(
+            r#"
+            SELECT 'player' AS kind, player_id AS id, username AS name, deleted_at
+            FROM players
+            WHERE deleted_at IS NOT NULL
+            UNION ALL
+            SELECT 'game', game_id, title, deleted_at
+            FROM games
+            WHERE deleted_at IS NOT NULL
+            UNION ALL
+            SELECT 'question', question_id, question_text, deleted_at
+            FROM questions
+            WHERE deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn restore_item(&self, kind: TrashKind, id: i32) -> Result<()> {
+        let (table, key) = trash_table(kind);
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = NULL WHERE {} = ? AND deleted_at IS NOT NULL",
+            table, key
+        ))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!(
+                "{} {} in the trash",
+                kind, id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn purge_item(&self, kind: TrashKind, id: i32) -> Result<()> {
+        let (table, key) = trash_table(kind);
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE {} = ? AND deleted_at IS NOT NULL",
+            table, key
+        ))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!(
+                "{} {} in the trash",
+                kind, id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn get_tags(&self) -> Result<Vec<Tag>> {
+        sqlx::query_as::<_, Tag>(
+            r#"
+            SELECT t.tag_id, t.name,
+                   (SELECT COUNT(*) FROM question_tags qt
+                    JOIN questions q ON q.question_id = qt.question_id
+                    WHERE qt.tag_id = t.tag_id AND q.deleted_at IS NULL) AS question_count,
+                   (SELECT COUNT(*) FROM game_tags gt
+                    JOIN games g ON g.game_id = gt.game_id
+                    WHERE gt.tag_id = t.tag_id AND g.deleted_at IS NULL) AS game_count
+            FROM tags t
+            ORDER BY t.name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_question_tags(&self, question_id: i32) -> Result<Vec<String>> {
+        self.linked_tags("question_tags", "question_id", question_id)
+            .await
+    }
+
+    async fn set_question_tags(&self, question_id: i32, tags: &[String]) -> Result<()> {
+        self.get_question(question_id).await?;
+        self.replace_tags("question_tags", "question_id", question_id, tags)
+            .await
+    }
+
+    async fn get_game_tags(&self, game_id: i32) -> Result<Vec<String>> {
+        self.linked_tags("game_tags", "game_id", game_id).await
+    }
+
+    async fn set_game_tags(&self, game_id: i32, tags: &[String]) -> Result<()> {
+        self.get_game(game_id).await?;
+        self.replace_tags("game_tags", "game_id", game_id, tags)
+            .await
+    }
+
+    async fn get_questions_by_tag(&self, tag: &str) -> Result<Vec<Question>> {
+        sqlx::query_as::<_, Question>(
+            r#"
+            SELECT q.question_id, q.question_text, q.correct_answer, q.kind, q.partial_credit,
+                   q.difficulty, q.time_limit_secs, q.explanation, q.source
+            FROM questions q
+            JOIN question_tags qt ON qt.question_id = q.question_id
+            JOIN tags t ON t.tag_id = qt.tag_id
+            WHERE t.name = ? AND q.deleted_at IS NULL
+            ORDER BY q.question_id
+            "#,
+        )
+        .bind(normalize_tag(tag))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_games_by_tag(&self, tag: &str) -> Result<Vec<Game>> {
+        sqlx::query_as::<_, Game>(
+            r#"
+            SELECT g.game_id, g.title, g.description, g.play_order, g.draw_count
+            FROM games g
+            JOIN game_tags gt ON gt.game_id = g.game_id
+            JOIN tags t ON t.tag_id = gt.tag_id
+            WHERE t.name = ? AND g.deleted_at IS NULL
+            ORDER BY g.game_id
+            "#,
+        )
+        .bind(normalize_tag(tag))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn login_admin(&self, username: &str, password: &str) -> Result<Admin> {
+        let admin = sqlx::query_as::<_, Admin>(
+            r#"
+            SELECT admin_id, username, password_hash, created_at
+            FROM admins
+            WHERE username = ?
+            "#,
+        )
+        .bind(username)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(TriviaError::Unauthorized)?;
+
+        match verify_password(password, &admin.password_hash) {
+            PasswordCheck::Valid => Ok(admin),
+            PasswordCheck::ValidLegacy => {
+                let password_hash = hash_password(password)?;
+                sqlx::query(
+                    r#"
+                    UPDATE admins
+                    SET password_hash = ?
+                    WHERE admin_id = ?
+                    "#,
+                )
+                .bind(&password_hash)
+                .bind(admin.admin_id)
+                .execute(&self.pool)
+                .await?;
+
+                Ok(Admin {
+                    password_hash,
+                    ..admin
+                })
+            }
+            PasswordCheck::Invalid => Err(TriviaError::Unauthorized),
+        }
+    }
+
+    async fn login_player(&self, username: &str, password: &str) -> Result<Player> {
+        let player = sqlx::query_as::<_, Player>(
+            r#"
+            SELECT player_id, username, password_hash, created_at
+            FROM players
+            WHERE username = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(username)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(TriviaError::Unauthorized)?;
+
+        match verify_password(password, &player.password_hash) {
+            PasswordCheck::Valid => Ok(player),
+            PasswordCheck::ValidLegacy => {
+                let password_hash = hash_password(password)?;
+                sqlx::query(
+                    r#"
+                    UPDATE players
+                    SET password_hash = ?
+                    WHERE player_id = ?
+                    "#,
+                )
+                .bind(&password_hash)
+                .bind(player.player_id)
+                .execute(&self.pool)
+                .await?;
+
+                Ok(Player {
+                    password_hash,
+                    ..player
+                })
+            }
+            PasswordCheck::Invalid => Err(TriviaError::Unauthorized),
+        }
+    }
+
+    async fn get_all_players(&self) -> Result<Vec<Player>> {
+        sqlx::query_as::<_, Player>(
+            "SELECT player_id, username, password_hash, created_at FROM players WHERE deleted_at IS NULL",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn delete_player(&self, player_id: i32) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE players
+            SET deleted_at = CURRENT_TIMESTAMP
+            WHERE player_id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(player_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Player {}", player_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn create_new_player(&self, username: &str, password: &str) -> Result<()> {
+        let password_hash = hash_password(password)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO players (username, password_hash)
+            VALUES (?, ?)
+            "#,
+        )
+        .bind(username)
+        .bind(password_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::DuplicateUsername(username.to_string())
+            } else {
+                e.into()
+            }
+        })?;
+
+        Ok(())
+    }
+
+    async fn get_game_questions(&self, game_id: i32) -> Result<Vec<GameQuestionFull>> {
+        sqlx::query_as::<_, GameQuestionFull>(
+            r#"
+            SELECT q.question_id, q.question_text, q.correct_answer, q.kind, q.partial_credit,
+                   q.difficulty, q.time_limit_secs, q.explanation, q.source, gq.question_order
+            FROM questions q
+            JOIN game_questions gq ON q.question_id = gq.question_id
+            WHERE gq.game_id = ? AND q.deleted_at IS NULL
+            ORDER BY gq.question_order
+            "#,
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_all_questions(&self) -> Result<Vec<Question>> {
+        sqlx::query_as::<_, Question>(
+            r#"
+            SELECT question_id, question_text, correct_answer, kind, partial_credit,
+                   difficulty, time_limit_secs, explanation, source
+            FROM questions
+            WHERE deleted_at IS NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_question_usage(&self) -> Result<Vec<QuestionUsage>> {
+        sqlx::query_as::<_, QuestionUsage>(
+            r#"
+            SELECT q.question_id, q.question_text, q.kind, q.difficulty,
+                   (SELECT COUNT(*) FROM game_questions gq
+                    JOIN games g ON g.game_id = gq.game_id
+                    WHERE gq.question_id = q.question_id AND g.deleted_at IS NULL) AS game_count,
+                   (SELECT COUNT(*) FROM session_answers sa
+                    WHERE sa.question_id = q.question_id) AS answer_count
+            FROM questions q
+            WHERE q.deleted_at IS NULL
+            ORDER BY q.question_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_games_using_question(&self, question_id: i32) -> Result<Vec<Game>> {
+        sqlx::query_as::<_, Game>(
+            r#"
+            SELECT g.game_id, g.title, g.description, g.play_order, g.draw_count
+            FROM games g
+            JOIN game_questions gq ON g.game_id = gq.game_id
+            WHERE gq.question_id = ? AND g.deleted_at IS NULL
+            ORDER BY g.title
+            "#,
+        )
+        .bind(question_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn search_questions(&self, search: &QuestionSearch, limit: i32) -> Result<Vec<Question>> {
+        let terms = search_terms(search)?;
+
+        let mut query = QueryBuilder::<Sqlite>::new(
+            r#"
+            SELECT q.question_id, q.question_text, q.correct_answer, q.kind, q.partial_credit,
+                   q.difficulty, q.time_limit_secs, q.explanation, q.source
+            FROM questions q
+            LEFT JOIN admins a ON a.admin_id = q.created_by
+            "#,
+        );
+        if terms.is_some() {
+            query.push(" JOIN questions_fts ON questions_fts.rowid = q.question_id");
+        }
+        query.push(" WHERE q.deleted_at IS NULL");
+
+        if let Some(terms) = &terms {
+            // Quoted so FTS5 reads each word literally, with * matching by prefix
+            let pattern: Vec<String> = terms.iter().map(|t| format!("\"{}\"*", t)).collect();
+            query
+                .push(" AND questions_fts MATCH ")
+                .push_bind(pattern.join(" "));
+        }
+        if let Some(tag) = &search.tag {
+            query
+                .push(
+                    " AND EXISTS (SELECT 1 FROM question_tags qt JOIN tags t ON t.tag_id = qt.tag_id \
+                     WHERE qt.question_id = q.question_id AND t.name = ",
+                )
+                .push_bind(normalize_tag(tag))
+                .push(")");
+        }
+        if let Some(author) = &search.author {
+            query.push(" AND a.username = ").push_bind(author.clone());
+        }
+        if let Some(kind) = search.kind {
+            query.push(" AND q.kind = ").push_bind(kind.as_str());
+        }
+        if let Some(from) = search.created_from {
+            query.push(" AND date(q.created_at) >= ").push_bind(from);
+        }
+        if let Some(to) = search.created_to {
+            query.push(" AND date(q.created_at) <= ").push_bind(to);
+        }
+
+        if terms.is_some() {
+            query.push(" ORDER BY questions_fts.rank, q.question_id");
+        } else {
+            query.push(" ORDER BY q.question_id");
+        }
+        query.push(" LIMIT ").push_bind(limit);
+
+        query
+            .build_query_as::<Question>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn add_question_to_game(&self, game_id: i32, question_id: i32, order: i32) -> Result<()> {
+        self.get_game(game_id).await?;
+        self.get_question(question_id).await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO game_questions (game_id, question_id, question_order)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(game_id)
+        .bind(question_id)
+        .bind(order)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::InvalidInput(format!(
+                    "Question {} is already in this game",
+                    question_id
+                ))
+            } else if is_foreign_key_violation(&e) {
+                TriviaError::NotFound(format!("Game {} or question {}", game_id, question_id))
+            } else {
+                e.into()
+            }
+        })?;
+
+        Ok(())
+    }
+
+    async fn set_question_explanation(
+        &self,
+        question_id: i32,
+        explanation: Option<&str>,
+        source: Option<&str>,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE questions
+            SET explanation = ?, source = ?
+            WHERE question_id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(explanation)
+        .bind(source)
+        .bind(question_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn set_question_difficulty(
+        &self,
+        question_id: i32,
+        difficulty: Difficulty,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE questions
+            SET difficulty = ?
+            WHERE question_id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(difficulty.as_str())
+        .bind(question_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn set_question_time_limit(&self, question_id: i32, seconds: Option<i32>) -> Result<()> {
+        validate_time_limit(seconds)?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE questions
+            SET time_limit_secs = ?
+            WHERE question_id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(seconds)
+        .bind(question_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_question(&self, question_id: i32) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE questions
+            SET deleted_at = CURRENT_TIMESTAMP
+            WHERE question_id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(question_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Question {}", question_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_game_question(&self, game_id: i32, question_id: i32) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM game_questions
+            WHERE game_id = ? AND question_id = ?
+            "#,
+        )
+        .bind(game_id)
+        .bind(question_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!(
+                "Question {} in game {}",
+                question_id, game_id
+            )));
+        }
+
+        self.renumber_game_questions(game_id).await?;
+
+        Ok(())
+    }
+
+    async fn update_question_order(
+        &self,
+        game_id: i32,
+        question_id: i32,
+        order: i32,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE game_questions
+            SET question_order = ?
+            WHERE game_id = ? AND question_id = ?
+              AND game_id IN (SELECT game_id FROM games WHERE deleted_at IS NULL)
+              AND question_id IN (SELECT question_id FROM questions WHERE deleted_at IS NULL)
+            "#,
+        )
+        .bind(order)
+        .bind(game_id)
+        .bind(question_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!(
+                "Question {} in game {}",
+                question_id, game_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn reorder_game_questions(&self, game_id: i32, question_ids: &[i32]) -> Result<()> {
+        check_question_order(&self.get_game_questions(game_id).await?, question_ids)?;
+        let mut tx = self.pool.begin().await?;
+
+        for (new_order, question_id) in question_ids.iter().enumerate() {
+            sqlx::query(
+                r#"
+                UPDATE game_questions
+                SET question_order = ?
+                WHERE game_id = ? AND question_id = ?
+                "#,
+            )
+            .bind(new_order as i32 + 1)
+            .bind(game_id)
+            .bind(question_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_max_question_order(&self, game_id: i32) -> Result<i32> {
+        sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT COALESCE(MAX(question_order), 0)
+            FROM game_questions
+            WHERE game_id = ?
+            "#,
+        )
+        .bind(game_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn create_question_with_details(
+        &self,
+        question: &NewQuestion,
+        admin_id: i32,
+    ) -> Result<i32> {
+        let tags = validate_new_question(question)?;
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO questions
+                (question_text, correct_answer, kind, partial_credit, difficulty,
+                 time_limit_secs, explanation, source, created_by)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(question.text.trim())
+        .bind(question.answer.correct_answer())
+        .bind(question.answer.kind().as_str())
+        .bind(question.answer.partial_credit())
+        .bind(question.difficulty.as_str())
+        .bind(question.time_limit_secs)
+        .bind(question.explanation.as_deref())
+        .bind(question.source.as_deref())
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await?;
+        let question_id = result.last_insert_rowid() as i32;
+
+        Self::insert_answer_rows(&mut tx, question_id, &question.answer).await?;
+        Self::link_tags(&mut tx, "question_tags", "question_id", question_id, &tags).await?;
+
+        tx.commit().await?;
+        Ok(question_id)
+    }
+
+    async fn create_question(&self, text: &str, answer: bool, admin_id: i32) -> Result<i32> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO questions (question_text, correct_answer, created_by)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(text)
+        .bind(answer)
+        .bind(admin_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid() as i32)
+    }
+
+    async fn create_multiple_choice_question(
+        &self,
+        text: &str,
+        choices: &[String],
+        correct_choice: usize,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_choices(choices, correct_choice)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO questions (question_text, correct_answer, kind, created_by)
+            VALUES (?, FALSE, ?, ?)
+            "#,
+        )
+        .bind(text)
+        .bind(QuestionKind::MultipleChoice.as_str())
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await?;
+        let question_id = result.last_insert_rowid() as i32;
+
+        let answer = AnswerSpec::MultipleChoice {
+            choices: choices.to_vec(),
+            correct_choice,
+        };
+        Self::insert_answer_rows(&mut tx, question_id, &answer).await?;
+
+        tx.commit().await?;
+        Ok(question_id)
+    }
+
+    async fn get_question_choices(&self, question_id: i32) -> Result<Vec<QuestionChoice>> {
+        sqlx::query_as::<_, QuestionChoice>(
+            r#"
+            SELECT choice_id, question_id, choice_order, choice_text, is_correct
+            FROM question_choices
+            WHERE question_id = ?
+            ORDER BY choice_order
+            "#,
+        )
+        .bind(question_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn create_free_text_question(
+        &self,
+        text: &str,
+        accepted: &[String],
+        max_distance: i32,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_accepted_answers(accepted, max_distance)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO questions (question_text, correct_answer, kind, created_by)
+            VALUES (?, FALSE, ?, ?)
+            "#,
+        )
+        .bind(text)
+        .bind(QuestionKind::FreeText.as_str())
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await?;
+        let question_id = result.last_insert_rowid() as i32;
+
+        let answer = AnswerSpec::FreeText {
+            accepted: accepted.to_vec(),
+            max_distance,
+        };
+        Self::insert_answer_rows(&mut tx, question_id, &answer).await?;
+
+        tx.commit().await?;
+        Ok(question_id)
+    }
+
+    async fn get_accepted_answers(&self, question_id: i32) -> Result<Vec<AcceptedAnswer>> {
+        sqlx::query_as::<_, AcceptedAnswer>(
+            r#"
+            SELECT accepted_answer_id, question_id, answer_text, max_distance
+            FROM accepted_answers
+            WHERE question_id = ?
+            ORDER BY accepted_answer_id
+            "#,
+        )
+        .bind(question_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn create_numeric_question(
+        &self,
+        text: &str,
+        target_value: f64,
+        tolerance: f64,
+        tolerance_percent: bool,
+        unit: Option<&str>,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_numeric_answer(target_value, tolerance, unit)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO questions (question_text, correct_answer, kind, created_by)
+            VALUES (?, FALSE, ?, ?)
+            "#,
+        )
+        .bind(text)
+        .bind(QuestionKind::Numeric.as_str())
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await?;
+        let question_id = result.last_insert_rowid() as i32;
+
+        let answer = AnswerSpec::Numeric {
+            target_value,
+            tolerance,
+            tolerance_percent,
+            unit: unit.map(str::to_string),
+        };
+        Self::insert_answer_rows(&mut tx, question_id, &answer).await?;
+
+        tx.commit().await?;
+        Ok(question_id)
+    }
+
+    async fn get_numeric_answer(&self, question_id: i32) -> Result<NumericAnswer> {
+        sqlx::query_as::<_, NumericAnswer>(
+            r#"
+            SELECT question_id, target_value, tolerance, tolerance_percent, unit
+            FROM numeric_answers
+            WHERE question_id = ?
+            "#,
+        )
+        .bind(question_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| TriviaError::NotFound(format!("Answer for question {}", question_id)))
+    }
+
+    async fn create_ordering_question(
+        &self,
+        text: &str,
+        items: &[String],
+        partial_credit: bool,
+        admin_id: i32,
+    ) -> Result<i32> {
+        validate_ordering_items(items)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO questions (question_text, correct_answer, kind, partial_credit, created_by)
+            VALUES (?, FALSE, ?, ?, ?)
+            "#,
+        )
+        .bind(text)
+        .bind(QuestionKind::Ordering.as_str())
+        .bind(partial_credit)
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await?;
+        let question_id = result.last_insert_rowid() as i32;
+
+        let answer = AnswerSpec::Ordering {
+            items: items.to_vec(),
+            partial_credit,
+        };
+        Self::insert_answer_rows(&mut tx, question_id, &answer).await?;
+
+        tx.commit().await?;
+        Ok(question_id)
+    }
+
+    async fn get_ordering_items(&self, question_id: i32) -> Result<Vec<OrderingItem>> {
+        sqlx::query_as::<_, OrderingItem>(
+            r#"
+            SELECT item_id, question_id, item_order, item_text
+            FROM ordering_items
+            WHERE question_id = ?
+            ORDER BY item_order
+            "#,
+        )
+        .bind(question_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_question(&self, question_id: i32) -> Result<Question> {
+        sqlx::query_as::<_, Question>(
+            r#"
+            SELECT question_id, question_text, correct_answer, kind, partial_credit,
+                   difficulty, time_limit_secs, explanation, source
+            FROM questions
+            WHERE question_id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(question_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| TriviaError::NotFound(format!("Question {}", question_id)))
+    }
+
+    async fn update_question(
+        &self,
+        question_id: i32,
+        text: &str,
+        answer: &AnswerSpec,
+        explanation: Option<&str>,
+        source: Option<&str>,
+        admin_id: i32,
+    ) -> Result<()> {
+        let current = self.get_question(question_id).await?;
+        let current_answer = self.get_answer_spec(&current).await?;
+        if !check_edit(&current, &current_answer, text, answer, explanation, source)? {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO question_revisions
+                (question_id, question_text, answer, explanation, source, edited_by)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(question_id)
+        .bind(&current.question_text)
+        .bind(current_answer.to_json()?)
+        .bind(&current.explanation)
+        .bind(&current.source)
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE questions
+            SET question_text = ?, correct_answer = ?, partial_credit = ?,
+                explanation = ?, source = ?
+            WHERE question_id = ?
+            "#,
+        )
+        .bind(text)
+        .bind(answer.correct_answer())
+        .bind(answer.partial_credit())
+        .bind(explanation)
+        .bind(source)
+        .bind(question_id)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::delete_answer_rows(&mut tx, question_id).await?;
+        Self::insert_answer_rows(&mut tx, question_id, answer).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_question_revisions(&self, question_id: i32) -> Result<Vec<QuestionRevision>> {
+        sqlx::query_as::<_, QuestionRevision>(
+            r#"
+            SELECT r.revision_id, r.question_id, r.question_text, r.answer,
+                   r.explanation, r.source, r.edited_by, a.username AS editor, r.edited_at
+            FROM question_revisions r
+            LEFT JOIN admins a ON a.admin_id = r.edited_by
+            WHERE r.question_id = ?
+            ORDER BY r.revision_id DESC
+            "#,
+        )
+        .bind(question_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn create_game(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        admin_id: i32,
+    ) -> Result<i32> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO games (title, description, created_by)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(title)
+        .bind(description)
+        .bind(admin_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::DuplicateTitle(title.to_string())
+            } else {
+                e.into()
+            }
+        })?;
+
+        Ok(result.last_insert_rowid() as i32)
+    }
+
+    async fn create_game_with_tags(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        tags: &[String],
+        admin_id: i32,
+    ) -> Result<i32> {
+        let tags = normalize_tags(tags)?;
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO games (title, description, created_by)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(title)
+        .bind(description)
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::DuplicateTitle(title.to_string())
+            } else {
+                e.into()
+            }
+        })?;
+        let game_id = result.last_insert_rowid() as i32;
+
+        Self::link_tags(&mut tx, "game_tags", "game_id", game_id, &tags).await?;
+
+        tx.commit().await?;
+        Ok(game_id)
+    }
+
+    async fn get_game(&self, game_id: i32) -> Result<Game> {
+        sqlx::query_as::<_, Game>("SELECT game_id, title, description, play_order, draw_count FROM games WHERE game_id = ? AND deleted_at IS NULL")
+            .bind(game_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| TriviaError::NotFound(format!("Game {}", game_id)))
+    }
+
+    async fn update_game(
+        &self,
+        game_id: i32,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE games
+            SET title = ?, description = ?
+            WHERE game_id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(title)
+        .bind(description)
+        .bind(game_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::DuplicateTitle(title.to_string())
+            } else {
+                e.into()
+            }
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn update_game_with_tags(
+        &self,
+        game_id: i32,
+        title: &str,
+        description: Option<&str>,
+        tags: Option<&[String]>,
+    ) -> Result<()> {
+        let tags = tags.map(normalize_tags).transpose()?;
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE games
+            SET title = ?, description = ?
+            WHERE game_id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(title)
+        .bind(description)
+        .bind(game_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                TriviaError::DuplicateTitle(title.to_string())
+            } else {
+                e.into()
+            }
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        if let Some(tags) = tags {
+            sqlx::query("DELETE FROM game_tags WHERE game_id = ?")
+                .bind(game_id)
+                .execute(&mut *tx)
+                .await?;
+            Self::link_tags(&mut tx, "game_tags", "game_id", game_id, &tags).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_game_usage(&self, game_id: i32) -> Result<GameUsage> {
+        sqlx::query_as::<_, GameUsage>(
+            r#"
+            SELECT
+                (SELECT COUNT(*)
+                 FROM game_questions gq
+                 JOIN questions q ON q.question_id = gq.question_id
+                 WHERE gq.game_id = ? AND q.deleted_at IS NULL) AS question_count,
+                (SELECT COUNT(*) FROM game_sessions WHERE game_id = ?) AS session_count
+            "#,
+        )
+        .bind(game_id)
+        .bind(game_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn set_game_play_order(
+        &self,
+        game_id: i32,
+        play_order: PlayOrder,
+        draw_count: Option<i32>,
+    ) -> Result<()> {
+        validate_play_order(play_order, draw_count)?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE games
+            SET play_order = ?, draw_count = ?
+            WHERE game_id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(play_order.as_str())
+        .bind(draw_count)
+        .bind(game_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_game(&self, game_id: i32) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE games
+            SET deleted_at = CURRENT_TIMESTAMP
+            WHERE game_id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(game_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn start_session(
+        &self,
+        player_id: i32,
+        game_id: i32,
+        mode: GameMode,
+        seed: Option<i64>,
+    ) -> Result<i32> {
+        let game = self.get_game(game_id).await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO game_sessions (player_id, game_id, mode, seed, play_order, draw_count)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(player_id)
+        .bind(game_id)
+        .bind(mode.as_str())
+        .bind(seed)
+        .bind(game.play_order.as_str())
+        .bind(game.draw_count)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid() as i32)
+    }
+
+    async fn record_answer(
+        &self,
+        session_id: i32,
+        question_id: i32,
+        submitted_answer: &str,
+        is_correct: bool,
+        score: f64,
+        response_time_ms: i32,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO session_answers
+                (session_id, question_id, submitted_answer, is_correct, score, response_time_ms)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(session_id)
+        .bind(question_id)
+        .bind(submitted_answer)
+        .bind(is_correct)
+        .bind(score)
+        .bind(response_time_ms)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn finish_session(
+        &self,
+        session_id: i32,
+        correct_answers: i32,
+        total_questions: i32,
+        score: f64,
+        max_score: f64,
+        bonus: f64,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE game_sessions
+            SET ended_at = CURRENT_TIMESTAMP, correct_answers = ?, total_questions = ?, score = ?,
+                max_score = ?, bonus = ?
+            WHERE session_id = ?
+            "#,
+        )
+        .bind(correct_answers)
+        .bind(total_questions)
+        .bind(score)
+        .bind(max_score)
+        .bind(bonus)
+        .bind(session_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TriviaError::NotFound(format!("Session {}", session_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn get_session(&self, session_id: i32) -> Result<GameSession> {
+        sqlx::query_as::<_, GameSession>(
+            r#"
+            SELECT session_id, player_id, game_id, mode, seed, play_order, draw_count,
+                   started_at, ended_at, correct_answers, total_questions, score, max_score, bonus
+            FROM game_sessions
+            WHERE session_id = ?
+            "#,
+        )
+        .bind(session_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| TriviaError::NotFound(format!("Session {}", session_id)))
+    }
+
+    async fn get_player_sessions(&self, player_id: i32) -> Result<Vec<GameSession>> {
+        sqlx::query_as::<_, GameSession>(
+            r#"
+            SELECT session_id, player_id, game_id, mode, seed, play_order, draw_count,
+                   started_at, ended_at, correct_answers, total_questions, score, max_score, bonus
+            FROM game_sessions
+            WHERE player_id = ?
+            ORDER BY started_at DESC, session_id DESC
+            "#,
+        )
+        .bind(player_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_session_answers(&self, session_id: i32) -> Result<Vec<SessionAnswer>> {
+        sqlx::query_as::<_, SessionAnswer>(
+            r#"
+            SELECT answer_id, session_id, question_id, submitted_answer,
+                   is_correct, score, response_time_ms, answered_at
+            FROM session_answers
+            WHERE session_id = ?
+            ORDER BY answer_id
+            "#,
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_game_leaderboard(
+        &self,
+        game_id: i32,
+        mode: GameMode,
+        basis: LeaderboardBasis,
+        limit: i32,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let latest = basis == LeaderboardBasis::Latest;
+
+        sqlx::query_as::<_, LeaderboardEntry>(
+            r#"
+            SELECT
+                ROW_NUMBER() OVER (
+                    ORDER BY chosen.score + chosen.bonus DESC, chosen.time_ms ASC, chosen.ended_at ASC
+                ) AS rank,
+                chosen.player_id,
+                chosen.username,
+                chosen.correct_answers,
+                chosen.total_questions,
+                chosen.score,
+                chosen.max_score,
+                chosen.bonus,
+                chosen.time_ms,
+                1 AS games_played
+            FROM (
+                SELECT s.player_id, p.username, s.correct_answers, s.total_questions,
+                       s.score, s.max_score, s.bonus, s.ended_at, COALESCE(t.time_ms, 0) AS time_ms,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY s.player_id
+                           ORDER BY CASE WHEN ? THEN s.ended_at END DESC,
+                                    s.score + s.bonus DESC,
+                                    COALESCE(t.time_ms, 0) ASC,
+                                    s.ended_at ASC
+                       ) AS attempt
+                FROM game_sessions s
+                JOIN players p ON p.player_id = s.player_id
+                LEFT JOIN (
+                    SELECT session_id, SUM(response_time_ms) AS time_ms
+                    FROM session_answers
+                    GROUP BY session_id
+                ) t ON t.session_id = s.session_id
+                WHERE s.game_id = ? AND s.mode = ? AND s.ended_at IS NOT NULL
+                  AND p.deleted_at IS NULL
+            ) chosen
+            WHERE chosen.attempt = 1
+            ORDER BY chosen.score + chosen.bonus DESC, chosen.time_ms ASC, chosen.ended_at ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(latest)
+        .bind(game_id)
+        .bind(mode.as_str())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_global_leaderboard(
+        &self,
+        mode: GameMode,
+        basis: LeaderboardBasis,
+        limit: i32,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let latest = basis == LeaderboardBasis::Latest;
+
+        sqlx::query_as::<_, LeaderboardEntry>(
+            r#"
+            SELECT
+                ROW_NUMBER() OVER (
+                    ORDER BY SUM(chosen.score + chosen.bonus) DESC,
+                             SUM(chosen.time_ms) ASC,
+                             MAX(chosen.ended_at) ASC
+                ) AS rank,
+                chosen.player_id,
+                chosen.username,
+                SUM(chosen.correct_answers) AS correct_answers,
+                SUM(chosen.total_questions) AS total_questions,
+                SUM(chosen.score) AS score,
+                SUM(chosen.max_score) AS max_score,
+                SUM(chosen.bonus) AS bonus,
+                SUM(chosen.time_ms) AS time_ms,
+                COUNT(*) AS games_played
+            FROM (
+                SELECT s.player_id, p.username, s.correct_answers, s.total_questions,
+                       s.score, s.max_score, s.bonus, s.ended_at, COALESCE(t.time_ms, 0) AS time_ms,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY s.player_id, s.game_id
+                           ORDER BY CASE WHEN ? THEN s.ended_at END DESC,
+                                    s.score + s.bonus DESC,
+                                    COALESCE(t.time_ms, 0) ASC,
+                                    s.ended_at ASC
+                       ) AS attempt
+                FROM game_sessions s
+                JOIN players p ON p.player_id = s.player_id
+                JOIN games g ON g.game_id = s.game_id
+                LEFT JOIN (
+                    SELECT session_id, SUM(response_time_ms) AS time_ms
+                    FROM session_answers
+                    GROUP BY session_id
+                ) t ON t.session_id = s.session_id
+                WHERE s.mode = ? AND s.ended_at IS NOT NULL
+                  AND p.deleted_at IS NULL AND g.deleted_at IS NULL
+            ) chosen
+            WHERE chosen.attempt = 1
+            GROUP BY chosen.player_id, chosen.username
+            ORDER BY SUM(chosen.score + chosen.bonus) DESC,
+                     SUM(chosen.time_ms) ASC,
+                     MAX(chosen.ended_at) ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(latest)
+        .bind(mode.as_str())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn record_survival_run(
+        &self,
+        player_id: i32,
+        game_id: Option<i32>,
+        lives: i32,
+        questions_answered: i32,
+        correct_answers: i32,
+        score: f64,
+    ) -> Result<i32> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO survival_runs
+                (player_id, game_id, lives, questions_answered, correct_answers, score)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(player_id)
+        .bind(game_id)
+        .bind(lives)
+        .bind(questions_answered)
+        .bind(correct_answers)
+        .bind(score)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid() as i32)
+    }
+
+    async fn get_survival_high_scores(
+        &self,
+        game_id: Option<i32>,
+        lives: i32,
+        limit: i32,
+    ) -> Result<Vec<SurvivalScore>> {
+        sqlx::query_as::<_, SurvivalScore>(
+            r#"
+            SELECT
+                ROW_NUMBER() OVER (
+                    ORDER BY best.correct_answers DESC, best.score DESC, best.played_at ASC
+                ) AS rank,
+                best.player_id,
+                best.username,
+                best.correct_answers,
+                best.questions_answered,
+                best.score,
+                best.played_at
+            FROM (
+                SELECT r.player_id, p.username, r.correct_answers, r.questions_answered,
+                       r.score, r.played_at,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY r.player_id
+                           ORDER BY r.correct_answers DESC, r.score DESC, r.played_at ASC
+                       ) AS attempt
+                FROM survival_runs r
+                JOIN players p ON p.player_id = r.player_id
+                LEFT JOIN games g ON g.game_id = r.game_id
+                WHERE r.game_id IS ? AND r.lives = ?
+                  AND p.deleted_at IS NULL AND g.deleted_at IS NULL
+            ) best
+            WHERE best.attempt = 1
+            ORDER BY best.correct_answers DESC, best.score DESC, best.played_at ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(game_id)
+        .bind(lives)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    // Practice times are bound as naive UTC so they are stored in the same
+    // text format as CURRENT_TIMESTAMP and compare correctly with it.
+
+    async fn get_practice_questions(
+        &self,
+        player_id: i32,
+        now: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<GameQuestionFull>> {
+        sqlx::query_as::<_, GameQuestionFull>(
+            r#"
+            SELECT q.question_id, q.question_text, q.correct_answer, q.kind, q.partial_credit,
+                   q.difficulty, q.time_limit_secs, q.explanation, q.source,
+                   ROW_NUMBER() OVER (
+                       ORDER BY queue.waiting_since, q.question_id
+                   ) AS question_order
+            FROM (
+                SELECT question_id, waiting_since
+                FROM (
+                    SELECT pc.question_id,
+                           CASE
+                               WHEN pc.due_at <= ? THEN pc.due_at
+                               -- Missed in a game since it was last practiced.
+                               ELSE (
+                                   SELECT MIN(sa.answered_at)
+                                   FROM session_answers sa
+                                   JOIN game_sessions s ON s.session_id = sa.session_id
+                                   WHERE s.player_id = pc.player_id
+                                     AND sa.question_id = pc.question_id
+                                     AND sa.is_correct = 0
+                                     AND sa.answered_at > pc.reviewed_at
+                               )
+                           END AS waiting_since
+                    FROM practice_cards pc
+                    WHERE pc.player_id = ?
+                ) cards
+                WHERE waiting_since IS NOT NULL
+                UNION ALL
+                SELECT sa.question_id, MIN(sa.answered_at) AS waiting_since
+                FROM session_answers sa
+                JOIN game_sessions s ON s.session_id = sa.session_id
+                WHERE s.player_id = ? AND sa.is_correct = 0
+                  AND NOT EXISTS (
+                      SELECT 1 FROM practice_cards pc
+                      WHERE pc.player_id = s.player_id AND pc.question_id = sa.question_id
+                  )
+                GROUP BY sa.question_id
+            ) queue
+            JOIN questions q ON q.question_id = queue.question_id
+            WHERE q.deleted_at IS NULL
+            ORDER BY queue.waiting_since, q.question_id
+            LIMIT ?
+            "#,
+        )
+        .bind(now.naive_utc())
+        .bind(player_id)
+        .bind(player_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_practice_card(
+        &self,
+        player_id: i32,
+        question_id: i32,
+    ) -> Result<Option<PracticeCard>> {
+        sqlx::query_as::<_, PracticeCard>(
+            r#"
+            SELECT player_id, question_id, repetitions, ease_factor, interval_days,
+                   due_at, reviewed_at
+            FROM practice_cards
+            WHERE player_id = ? AND question_id = ?
+            "#,
+        )
+        .bind(player_id)
+        .bind(question_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn save_practice_card(&self, card: &PracticeCard) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO practice_cards
+                (player_id, question_id, repetitions, ease_factor, interval_days,
+                 due_at, reviewed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (player_id, question_id) DO UPDATE SET
+                repetitions = excluded.repetitions,
+                ease_factor = excluded.ease_factor,
+                interval_days = excluded.interval_days,
+                due_at = excluded.due_at,
+                reviewed_at = excluded.reviewed_at
+            "#,
+        )
+        .bind(card.player_id)
+        .bind(card.question_id)
+        .bind(card.repetitions)
+        .bind(card.ease_factor)
+        .bind(card.interval_days)
+        .bind(card.due_at.naive_utc())
+        .bind(card.reviewed_at.naive_utc())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_next_practice_due(&self, player_id: i32) -> Result<Option<DateTime<Utc>>> {
+        sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            r#"
+            SELECT MIN(pc.due_at)
+            FROM practice_cards pc
+            JOIN questions q ON q.question_id = pc.question_id
+            WHERE pc.player_id = ? AND q.deleted_at IS NULL
+            "#,
+        )
+        .bind(player_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    /// A migrated in-memory database with one admin, whose id is returned.
+    async fn store() -> (SqliteStore, i32) {
+        // Every connection to :memory: opens a database of its own.
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let store = SqliteStore::new(pool);
+        store.migrate_up().await.unwrap();
+
+        let admin_id = sqlx::query("INSERT INTO admins (username, password_hash) VALUES (?, ?)")
+            .bind("admin")
+            .bind("secret")
+            .execute(&store.pool)
+            .await
+            .unwrap()
+            .last_insert_rowid() as i32;
+        (store, admin_id)
+    }
+
+    #[tokio::test]
+    async fn adding_an_unknown_question_is_not_found() {
+        let (store, admin_id) = store().await;
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+
+        assert!(matches!(
+            store.add_question_to_game(game_id, 999, 1).await,
+            Err(TriviaError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_new_game_is_saved_with_its_tags_or_not_at_all() {
+        let (store, admin_id) = store().await;
+
+        let game_id = store
+            .create_game_with_tags(
+                "Space",
+                None,
+                &["Planets".to_string(), "stars".to_string()],
+                admin_id,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_game_tags(game_id).await.unwrap(),
+            ["planets", "stars"]
+        );
+
+        let too_long = ["x".repeat(crate::models::MAX_TAG_LEN + 1)];
+        assert!(matches!(
+            store
+                .create_game_with_tags("Oceans", None, &too_long, admin_id)
+                .await,
+            Err(TriviaError::InvalidInput(_))
+        ));
+        assert_eq!(store.get_games().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn a_game_edit_is_saved_with_its_tags_or_not_at_all() {
+        let (store, admin_id) = store().await;
+        let game_id = store
+            .create_game_with_tags("Space", None, &["planets".to_string()], admin_id)
+            .await
+            .unwrap();
+
+        let too_long = ["x".repeat(crate::models::MAX_TAG_LEN + 1)];
+        assert!(matches!(
+            store
+                .update_game_with_tags(game_id, "Outer Space", None, Some(&too_long))
+                .await,
+            Err(TriviaError::InvalidInput(_))
+        ));
+        assert_eq!(store.get_game(game_id).await.unwrap().title, "Space");
+
+        store
+            .update_game_with_tags(game_id, "Outer Space", Some("Beyond the sky"), None)
+            .await
+            .unwrap();
+        assert_eq!(store.get_game_tags(game_id).await.unwrap(), ["planets"]);
+
+        store
+            .update_game_with_tags(game_id, "Outer Space", None, Some(&["Stars".to_string()]))
+            .await
+            .unwrap();
+        let game = store.get_game(game_id).await.unwrap();
+        assert_eq!(game.title, "Outer Space");
+        assert_eq!(game.description, None);
+        assert_eq!(store.get_game_tags(game_id).await.unwrap(), ["stars"]);
+    }
+
+    #[tokio::test]
+    async fn a_new_question_is_saved_with_its_details_or_not_at_all() {
+        let (store, admin_id) = store().await;
+        let mut question = NewQuestion {
+            text: "Largest planet".to_string(),
+            answer: AnswerSpec::FreeText {
+                accepted: vec!["Jupiter".to_string()],
+                max_distance: 1,
+            },
+            difficulty: Difficulty::Hard,
+            time_limit_secs: Some(20),
+            explanation: Some("It is over 300 Earth masses.".to_string()),
+            source: None,
+            tags: vec!["Space".to_string(), "planets".to_string()],
+        };
+
+        let question_id = store
+            .create_question_with_details(&question, admin_id)
+            .await
+            .unwrap();
+        let saved = store.get_question(question_id).await.unwrap();
+        assert_eq!(saved.difficulty, Difficulty::Hard);
+        assert_eq!(saved.time_limit_secs, Some(20));
+        assert_eq!(saved.explanation, question.explanation);
+        assert_eq!(
+            store.get_answer_spec(&saved).await.unwrap(),
+            question.answer
+        );
+        assert_eq!(
+            store.get_question_tags(question_id).await.unwrap(),
+            ["planets", "space"]
+        );
+
+        question.text = "Smallest planet".to_string();
+        question.tags = vec!["x".repeat(crate::models::MAX_TAG_LEN + 1)];
+        assert!(matches!(
+            store
+                .create_question_with_details(&question, admin_id)
+                .await,
+            Err(TriviaError::InvalidInput(_))
+        ));
+        assert_eq!(store.get_all_questions().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn game_usage_skips_trashed_questions() {
+        let (store, admin_id) = store().await;
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        for order in 1..=2 {
+            let question_id = store
+                .create_question(&format!("Question {}", order), true, admin_id)
+                .await
+                .unwrap();
+            store
+                .add_question_to_game(game_id, question_id, order)
+                .await
+                .unwrap();
+            if order == 2 {
+                store.delete_question(question_id).await.unwrap();
+            }
+        }
+
+        assert_eq!(
+            store.get_game_usage(game_id).await.unwrap().question_count,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn a_bad_reorder_keeps_the_old_order() {
+        let (store, admin_id) = store().await;
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        let mut ids = Vec::new();
+        for order in 1..=3 {
+            let question_id = store
+                .create_question(&format!("Question {}", order), true, admin_id)
+                .await
+                .unwrap();
+            store
+                .add_question_to_game(game_id, question_id, order)
+                .await
+                .unwrap();
+            ids.push(question_id);
+        }
+        let order = |questions: Vec<GameQuestionFull>| -> Vec<i32> {
+            questions.iter().map(|q| q.question_id).collect()
+        };
+
+        assert!(matches!(
+            store
+                .reorder_game_questions(game_id, &[ids[2], ids[0]])
+                .await,
+            Err(TriviaError::InvalidInput(_))
+        ));
+        assert_eq!(order(store.get_game_questions(game_id).await.unwrap()), ids);
+
+        ids.reverse();
+        store.reorder_game_questions(game_id, &ids).await.unwrap();
+        assert_eq!(order(store.get_game_questions(game_id).await.unwrap()), ids);
+    }
+
+    #[tokio::test]
+    async fn trashed_games_and_questions_cannot_be_used() {
+        let (store, admin_id) = store().await;
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        let question_id = store
+            .create_question("Sky is blue", true, admin_id)
+            .await
+            .unwrap();
+
+        store.delete_question(question_id).await.unwrap();
+        assert!(matches!(
+            store.add_question_to_game(game_id, question_id, 1).await,
+            Err(TriviaError::NotFound(_))
+        ));
+
+        store.create_new_player("player", "secret").await.unwrap();
+        let player_id = store
+            .login_player("player", "secret")
+            .await
+            .unwrap()
+            .player_id;
+        store.delete_game(game_id).await.unwrap();
+        assert!(matches!(
+            store
+                .start_session(player_id, game_id, GameMode::Regular, None)
+                .await,
+            Err(TriviaError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn trashed_games_and_questions_cannot_be_edited() {
+        let (store, admin_id) = store().await;
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        let question_id = store
+            .create_question("Sky is blue", true, admin_id)
+            .await
+            .unwrap();
+        store
+            .add_question_to_game(game_id, question_id, 1)
+            .await
+            .unwrap();
+        store.delete_question(question_id).await.unwrap();
+        store.delete_game(game_id).await.unwrap();
+
+        let results = [
+            store.update_game(game_id, "Renamed", None).await,
+            store
+                .set_game_play_order(game_id, PlayOrder::Shuffled, None)
+                .await,
+            store
+                .set_question_explanation(question_id, Some("Rayleigh scattering"), None)
+                .await,
+            store
+                .set_question_difficulty(question_id, Difficulty::Hard)
+                .await,
+            store.set_question_time_limit(question_id, Some(30)).await,
+            store.update_question_order(game_id, question_id, 2).await,
+        ];
+        for result in results {
+            assert!(matches!(result, Err(TriviaError::NotFound(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn timed_leaderboards_rank_by_score_plus_bonus() {
+        let (store, admin_id) = store().await;
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        let question_id = store
+            .create_question("Sky is blue", true, admin_id)
+            .await
+            .unwrap();
+        store
+            .add_question_to_game(game_id, question_id, 1)
+            .await
+            .unwrap();
+
+        // Alice answers faster, but only Bob earns a bonus.
+        for (username, time_ms, bonus) in [("alice", 1000, 0.0), ("bob", 2000, 0.5)] {
+            store.create_new_player(username, "secret").await.unwrap();
+            let player = store.login_player(username, "secret").await.unwrap();
+            let session_id = store
+                .start_session(player.player_id, game_id, GameMode::Timed, None)
+                .await
+                .unwrap();
+            store
+                .record_answer(session_id, question_id, "true", true, 1.0, time_ms)
+                .await
+                .unwrap();
+            store
+                .finish_session(session_id, 1, 1, 2.0, 2.0, bonus)
+                .await
+                .unwrap();
+        }
+
+        let board = store
+            .get_game_leaderboard(game_id, GameMode::Timed, LeaderboardBasis::Best, 10)
+            .await
+            .unwrap();
+        let rows: Vec<(&str, f64, f64)> = board
+            .iter()
+            .map(|e| (e.username.as_str(), e.score, e.bonus))
+            .collect();
+        assert_eq!(rows, [("bob", 2.0, 0.5), ("alice", 2.0, 0.0)]);
+
+        let global = store
+            .get_global_leaderboard(GameMode::Timed, LeaderboardBasis::Best, 10)
+            .await
+            .unwrap();
+        assert_eq!(global[0].username, "bob");
+        assert_eq!(global[0].max_score, 2.0);
+    }
+
+    #[tokio::test]
+    async fn a_new_miss_brings_a_practiced_question_back() {
+        let (store, admin_id) = store().await;
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        let question_id = store
+            .create_question("Sky is blue", true, admin_id)
+            .await
+            .unwrap();
+        store
+            .add_question_to_game(game_id, question_id, 1)
+            .await
+            .unwrap();
+        store.create_new_player("player", "secret").await.unwrap();
+        let player_id = store
+            .login_player("player", "secret")
+            .await
+            .unwrap()
+            .player_id;
+
+        let now = Utc::now();
+        let mut card = PracticeCard::new(player_id, question_id, now - Duration::hours(1));
+        card.due_at = now + Duration::days(6);
+        store.save_practice_card(&card).await.unwrap();
+        assert!(store
+            .get_practice_questions(player_id, now, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let session_id = store
+            .start_session(player_id, game_id, GameMode::Regular, None)
+            .await
+            .unwrap();
+        store
+            .record_answer(session_id, question_id, "false", false, 0.0, 1000)
+            .await
+            .unwrap();
+        let queue = store
+            .get_practice_questions(player_id, Utc::now(), 10)
+            .await
+            .unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].question_id, question_id);
+
+        // Practicing it again clears the miss.
+        card.reviewed_at = Utc::now() + Duration::minutes(1);
+        store.save_practice_card(&card).await.unwrap();
+        assert!(store
+            .get_practice_questions(player_id, Utc::now(), 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
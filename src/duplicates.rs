@@ -0,0 +1,279 @@
+//! Spotting questions in the bank that repeat one another.
+
+use crate::matching;
+use crate::models::Question;
+use std::collections::HashSet;
+
+/// Similarity at or above which two questions are reported as near-duplicates.
+pub const NEAR_DUPLICATE_SIMILARITY: f64 = 0.8;
+
+/// Words too common to say anything about what a question asks.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "did", "do", "does", "for", "from", "has",
+    "have", "how", "in", "is", "it", "its", "of", "on", "or", "that", "the", "these", "this",
+    "those", "to", "was", "were", "what", "when", "where", "which", "who", "why", "with",
+];
+
+/// Words this long or longer may differ by one typo and still count as the
+/// same word.
+const MIN_FUZZY_WORD_LEN: usize = 5;
+
+/// A question in the bank that resembles another question.
+#[derive(Debug, Clone)]
+pub struct SimilarQuestion {
+    pub question: Question,
+    /// From 0 to 1; 1 when the texts have the same meaningful words.
+    pub similarity: f64,
+    /// Whether the texts are identical once case, spacing and punctuation
+    /// are ignored.
+    pub exact: bool,
+}
+
+/// A question's text reduced to what is compared.
+struct Fingerprint {
+    normalized: String,
+    words: Vec<String>,
+}
+
+impl Fingerprint {
+    fn new(text: &str) -> Self {
+        let mut words: Vec<String> = Vec::new();
+        for word in matching::words(text) {
+            if !STOP_WORDS.contains(&word.as_str()) && !words.contains(&word) {
+                words.push(word);
+            }
+        }
+        Fingerprint {
+            normalized: matching::normalize(text),
+            words,
+        }
+    }
+
+    fn is_exact(&self, other: &Fingerprint) -> bool {
+        !self.normalized.is_empty() && self.normalized == other.normalized
+    }
+
+    /// Share of the two texts' meaningful words that appear in the other
+    /// text, allowing a typo in longer words.
+    fn similarity(&self, other: &Fingerprint) -> f64 {
+        if self.is_exact(other) {
+            return 1.0;
+        }
+        let total = self.words.len() + other.words.len();
+        if total == 0 {
+            return 0.0;
+        }
+        let matched =
+            count_found(&self.words, &other.words) + count_found(&other.words, &self.words);
+        matched as f64 / total as f64
+    }
+}
+
+/// How many of `words` have a match among `other`.
+fn count_found(words: &[String], other: &[String]) -> usize {
+    words
+        .iter()
+        .filter(|word| other.iter().any(|o| same_word(word, o)))
+        .count()
+}
+
+fn same_word(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    let fuzzy =
+        |w: &str| w.chars().count() >= MIN_FUZZY_WORD_LEN && !w.chars().any(|c| c.is_ascii_digit());
+    fuzzy(a) && fuzzy(b) && matching::edit_distance(a, b) <= 1
+}
+
+/// Questions in `questions` whose text duplicates or nearly duplicates
+/// `text`, most similar first.
+pub fn find_similar(text: &str, questions: &[Question]) -> Vec<SimilarQuestion> {
+    let fingerprint = Fingerprint::new(text);
+
+    let mut similar: Vec<SimilarQuestion> = questions
+        .iter()
+        .filter_map(|question| {
+            let other = Fingerprint::new(&question.question_text);
+            let similarity = fingerprint.similarity(&other);
+            (similarity >= NEAR_DUPLICATE_SIMILARITY).then(|| SimilarQuestion {
+                question: question.clone(),
+                similarity,
+                exact: fingerprint.is_exact(&other),
+            })
+        })
+        .collect();
+    similar.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then(a.question.question_id.cmp(&b.question.question_id))
+    });
+    similar
+}
+
+/// Groups questions that duplicate or nearly duplicate each other, directly
+/// or through another question in the group. Each group is ordered by ID and
+/// the groups by their first ID; questions with no likeness are left out.
+pub fn find_clusters(questions: &[Question]) -> Vec<Vec<Question>> {
+    let fingerprints: Vec<Fingerprint> = questions
+        .iter()
+        .map(|q| Fingerprint::new(&q.question_text))
+        .collect();
+
+    // Union-find over question indexes
+    let mut parent: Vec<usize> = (0..questions.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut linked = HashSet::new();
+    for i in 0..questions.len() {
+        for j in i + 1..questions.len() {
+            if fingerprints[i].similarity(&fingerprints[j]) >= NEAR_DUPLICATE_SIMILARITY {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                parent[a.max(b)] = a.min(b);
+                linked.insert(i);
+                linked.insert(j);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<Question>> = Vec::new();
+    let mut cluster_of: Vec<Option<usize>> = vec![None; questions.len()];
+    let mut order: Vec<usize> = linked.into_iter().collect();
+    order.sort_by_key(|&i| questions[i].question_id);
+    for i in order {
+        let r = root(&mut parent, i);
+        match cluster_of[r] {
+            Some(c) => clusters[c].push(questions[i].clone()),
+            None => {
+                cluster_of[r] = Some(clusters.len());
+                clusters.push(vec![questions[i].clone()]);
+            }
+        }
+    }
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Answer, Difficulty, QuestionKind};
+
+    fn question(question_id: i32, text: &str) -> Question {
+        Question {
+            question_id,
+            question_text: text.to_string(),
+            correct_answer: Answer::True,
+            kind: QuestionKind::TrueFalse,
+            partial_credit: false,
+            difficulty: Difficulty::Medium,
+            time_limit_secs: None,
+            explanation: None,
+            source: None,
+        }
+    }
+
+    fn ids(questions: &[Question]) -> Vec<i32> {
+        questions.iter().map(|q| q.question_id).collect()
+    }
+
+    #[test]
+    fn exact_duplicates_ignore_case_spacing_and_punctuation() {
+        let bank = [
+            question(1, "Paris is the capital of France."),
+            question(2, "Berlin is the capital of Germany."),
+        ];
+
+        let similar = find_similar("  paris is the CAPITAL of france", &bank);
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].question.question_id, 1);
+        assert!(similar[0].exact);
+        assert_eq!(similar[0].similarity, 1.0);
+    }
+
+    #[test]
+    fn a_typo_in_a_longer_word_is_a_near_duplicate() {
+        let bank = [question(1, "Jupiter is the largest planet.")];
+
+        // One swapped pair of letters.
+        let similar = find_similar("Jupiter is the largset planet.", &bank);
+        assert_eq!(similar.len(), 1);
+        assert!(!similar[0].exact);
+        assert_eq!(similar[0].similarity, 1.0);
+
+        // Two edits in one word is a different word.
+        let similar = find_similar("Jupiter is the lergust planet.", &bank);
+        assert!(similar.is_empty());
+
+        // Short words and numbers must match exactly.
+        let bank = [question(1, "The sun rises in the east.")];
+        assert!(find_similar("The sun rises in the west.", &bank).is_empty());
+        let bank = [question(1, "The Moon is 384400 km away.")];
+        assert!(find_similar("The Moon is 384401 km away.", &bank).is_empty());
+    }
+
+    #[test]
+    fn shared_stop_words_do_not_make_questions_similar() {
+        let bank = [
+            question(1, "What is the capital of France?"),
+            question(2, "What is it?"),
+        ];
+
+        assert!(find_similar("What is the speed of light?", &bank).is_empty());
+        assert!(find_similar("What is that?", &bank).is_empty());
+        assert!(find_clusters(&bank).is_empty());
+    }
+
+    #[test]
+    fn similarity_at_the_threshold_is_reported() {
+        let bank = [question(1, "alpha bravo charlie delta echo")];
+
+        // Eight of the ten words have a match.
+        let similar = find_similar("alpha bravo charlie delta foxtrot", &bank);
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].similarity, NEAR_DUPLICATE_SIMILARITY);
+
+        // Six of eight falls short.
+        let bank = [question(1, "alpha bravo charlie delta")];
+        assert!(find_similar("alpha bravo charlie foxtrot", &bank).is_empty());
+    }
+
+    #[test]
+    fn similar_questions_come_most_similar_first() {
+        let bank = [
+            question(1, "alpha bravo charlie delta foxtrot"),
+            question(2, "alpha bravo charlie delta echo"),
+        ];
+
+        let similar = find_similar("alpha bravo charlie delta echo", &bank);
+        assert_eq!(
+            similar
+                .iter()
+                .map(|s| s.question.question_id)
+                .collect::<Vec<_>>(),
+            [2, 1]
+        );
+    }
+
+    #[test]
+    fn clusters_join_questions_linked_through_another() {
+        let bank = [
+            question(5, "alpha bravo charlie delta echo"),
+            question(1, "alpha bravo charlie delta foxtrot"),
+            question(3, "alpha bravo charlie golf foxtrot"),
+            question(2, "Who painted the Mona Lisa?"),
+            question(4, "Who painted the Mona Lisa"),
+        ];
+
+        // 5 and 3 only share three of five words, but both resemble 1.
+        let clusters = find_clusters(&bank);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(ids(&clusters[0]), [1, 3, 5]);
+        assert_eq!(ids(&clusters[1]), [2, 4]);
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum TriviaError {
+    /// A row the caller asked for does not exist, e.g. `"game 4"`.
+    NotFound(String),
+    DuplicateUsername(String),
+    DuplicateTitle(String),
+    InvalidInput(String),
+    Unauthorized,
+    /// Stdin was closed, so no more input can be read.
+    InputClosed,
+    Database(sqlx::Error),
+    Password(bcrypt::BcryptError),
+    Io(std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, TriviaError>;
+
+impl fmt::Display for TriviaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TriviaError::NotFound(what) => write!(f, "{} not found", what),
+            TriviaError::DuplicateUsername(username) => {
+                write!(f, "Username '{}' is already taken", username)
+            }
+            TriviaError::DuplicateTitle(title) => {
+                write!(f, "A game titled '{}' already exists", title)
+            }
+            TriviaError::InvalidInput(message) => write!(f, "Invalid input: {}", message),
+            TriviaError::Unauthorized => write!(f, "Not authorized"),
+            TriviaError::InputClosed => write!(f, "Input closed"),
+            TriviaError::Database(e) => write!(f, "Database error: {}", e),
+            TriviaError::Password(e) => write!(f, "Password hashing failed: {}", e),
+            TriviaError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TriviaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TriviaError::Database(e) => Some(e),
+            TriviaError::Password(e) => Some(e),
+            TriviaError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<sqlx::Error> for TriviaError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::RowNotFound => TriviaError::NotFound("Record".to_string()),
+            e => TriviaError::Database(e),
+        }
+    }
+}
+
+impl From<bcrypt::BcryptError> for TriviaError {
+    fn from(value: bcrypt::BcryptError) -> Self {
+        TriviaError::Password(value)
+    }
+}
+
+impl From<std::io::Error> for TriviaError {
+    fn from(value: std::io::Error) -> Self {
+        TriviaError::Io(value)
+    }
+}
+
+/// Returns true if the error was raised by a UNIQUE or PRIMARY KEY constraint.
+pub fn is_unique_violation(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(db_err) if db_err.is_unique_violation())
+}
+
+/// Returns true if the error was raised by a FOREIGN KEY constraint, i.e. a
+/// referenced row doesn't exist.
+pub fn is_foreign_key_violation(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation())
+}
@@ -1,69 +1,186 @@
 use crate::{
     clear_screen,
-    db::Database,
-    models::{Admin, Player},
+    config::GameConfig,
+    db::TriviaStore,
+    duplicates,
+    error::{Result, TriviaError},
+    input::LineReader,
+    matching,
+    models::{
+        choice_label, parse_choice, parse_permutation, parse_tags, Admin, AnswerKey, AnswerSpec,
+        Difficulty, Game, GameMode, GameQuestionFull, LeaderboardBasis, NewQuestion, PlayOrder,
+        Player, PracticeCard, QuestionKind, QuestionSearch, QuestionUsage, Tag, TrashKind,
+        MAX_CHOICES, MAX_ORDERING_ITEMS, MAX_TIME_LIMIT_SECS, MIN_CHOICES, MIN_ORDERING_ITEMS,
+        MIN_TIME_LIMIT_SECS,
+    },
 };
+use chrono::{NaiveDate, Utc};
+use rand::seq::SliceRandom;
 use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// Length of `session_answers.submitted_answer`.
+const MAX_SUBMITTED_ANSWER_LEN: usize = 255;
+/// Number of rows shown on the leaderboard screen.
+const LEADERBOARD_SIZE: i32 = 10;
+/// Most questions a question bank search lists.
+const SEARCH_LIMIT: i32 = 50;
+/// Most questions in one practice round.
+const PRACTICE_ROUND_SIZE: i32 = 10;
+/// Share of a question's points a correct answer in timed mode can add as a
+/// speed bonus. The bonus shrinks to nothing as the time runs out.
+const SPEED_BONUS_SHARE: f64 = 0.5;
+
+/// One answered question, kept for the end-of-game review.
+struct ReviewItem<'a> {
+    question: &'a GameQuestionFull,
+    key: AnswerKey,
+    response: String,
+    credit: f64,
+    timed_out: bool,
+}
 
-pub struct GameManager {
-    db: Database,
+pub struct GameManager<S: TriviaStore> {
+    db: S,
+    settings: GameConfig,
+    input: LineReader,
     current_admin: Option<Admin>,
     current_player: Option<Player>,
 }
 
-impl GameManager {
-    pub fn new(db: Database) -> Self {
+impl<S: TriviaStore> GameManager<S> {
+    pub fn new(db: S, settings: GameConfig) -> Self {
+        Self::with_input(db, settings, LineReader::new())
+    }
+
+    /// Like [`Self::new`], but reads from `input` instead of stdin.
+    pub fn with_input(db: S, settings: GameConfig, input: LineReader) -> Self {
         Self {
             db,
+            settings,
+            input,
             current_admin: None,
             current_player: None,
         }
     }
 
-    pub fn get_user_input(&self, prompt: &str) -> String {
+    pub async fn get_user_input(&self, prompt: &str) -> Result<String> {
+        print!("{}", prompt);
+        io::stdout().flush()?;
+        Ok(self.input.read_line().await?.trim().to_string())
+    }
+
+    /// Like [`Self::get_user_input`], but gives up at `deadline`, redrawing
+    /// the countdown on the top line of the screen every second. Returns
+    /// `None` if the time runs out first.
+    async fn get_timed_input(&self, prompt: &str, deadline: Instant) -> Result<Option<String>> {
         print!("{}", prompt);
-        io::stdout().flush().unwrap();
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
-        input.trim().to_string()
+        io::stdout().flush()?;
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            draw_countdown(left)?;
+            if left.is_zero() {
+                return Ok(None);
+            }
+            // Wake on the next whole second so the countdown stays in step.
+            let tick = match left.subsec_nanos() {
+                0 => Duration::from_secs(1),
+                nanos => Duration::from_nanos(nanos.into()),
+            };
+            let line = self
+                .input
+                .read_line_until(tokio::time::Instant::now() + tick)
+                .await?;
+            if let Some(line) = line {
+                return Ok(Some(line.trim().to_string()));
+            }
+        }
+    }
+
+    /// Reads an answer, against the clock if there is a deadline.
+    async fn get_answer_input(
+        &self,
+        prompt: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Option<String>> {
+        match deadline {
+            Some(deadline) => self.get_timed_input(prompt, deadline).await,
+            None => self.get_user_input(prompt).await.map(Some),
+        }
+    }
+
+    /// The time limit for a question in timed mode.
+    fn time_limit(&self, question: &GameQuestionFull) -> Duration {
+        let seconds = question
+            .time_limit_secs
+            .unwrap_or(self.settings.time_limit_secs)
+            .clamp(MIN_TIME_LIMIT_SECS, MAX_TIME_LIMIT_SECS);
+        Duration::from_secs(seconds as u64)
+    }
+
+    /// Shows an error and waits for the player to carry on. A closed stdin
+    /// can't be reported that way, so it is passed back up instead.
+    async fn report_error(&self, error: TriviaError) -> Result<()> {
+        if let TriviaError::InputClosed = error {
+            return Err(error);
+        }
+        println!("\nError: {}", error);
+        self.get_user_input("Press Enter to continue...").await?;
+        Ok(())
+    }
+
+    pub async fn run(&mut self) -> Result<()> {
+        // Closing stdin, e.g. with Ctrl-D, quits like choosing Exit.
+        match self.main_menu().await {
+            Err(TriviaError::InputClosed) => Ok(()),
+            result => result,
+        }
     }
 
-    pub async fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    async fn main_menu(&mut self) -> Result<()> {
         loop {
             clear_screen();
             println!("\n=== Trivia Game ===");
             println!("1. Admin Mode");
             println!("2. Player Mode");
             println!("3. Exit");
-            let input = self.get_user_input("Choose an option: ");
+            let input = self.get_user_input("Choose an option: ").await?;
 
-            match input.trim() {
-                "1" => self.admin_menu().await?,
-                "2" => self.player_menu().await?,
+            let result = match input.trim() {
+                "1" => self.admin_menu().await,
+                "2" => self.player_menu().await,
                 "3" => break,
-                _ => println!("Invalid option!"),
+                _ => {
+                    println!("Invalid option!");
+                    Ok(())
+                }
+            };
+
+            if let Err(e) = result {
+                self.report_error(e).await?;
             }
         }
         Ok(())
     }
 
-    async fn admin_menu(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    async fn admin_menu(&mut self) -> Result<()> {
         if self.current_admin.is_none() {
             clear_screen();
             println!("Must login to access!");
-            let username = self.get_user_input("Enter username: ");
+            let username = self.get_user_input("Enter username: ").await?;
 
-            let password = self.get_user_input("Enter password: ");
+            let password = self.get_user_input("Enter password: ").await?;
 
             match self.db.login_admin(username.trim(), password.trim()).await {
                 Ok(admin) => {
                     self.current_admin = Some(admin);
                     self.current_player = None;
                 }
-                Err(_) => {
+                Err(TriviaError::Unauthorized) => {
                     println!("Invalid username or password!");
                     return Ok(());
                 }
+                Err(e) => return Err(e),
             }
         }
 
@@ -71,30 +188,43 @@ impl GameManager {
             clear_screen();
             println!("\n=== Admin Menu ===");
             println!("1. Create Question");
-            println!("2. Create Game");
-            println!("3. Edit Game");
-            println!("4. Create User");
-            println!("5. Delete User");
-            println!("6. Back");
-            let input = self.get_user_input("Choose an option: ");
-
-            match input.trim() {
-                "1" => self.create_question().await?,
-                "2" => self.create_game().await?,
-                "3" => self.edit_games().await?,
-                "4" => self.create_player().await?,
-                "5" => self.delete_players().await?,
-                "6" => break,
-                _ => println!("Invalid option!"),
+            println!("2. Manage Questions");
+            println!("3. Create Game");
+            println!("4. Edit Game");
+            println!("5. Create User");
+            println!("6. Delete User");
+            println!("7. Trash");
+            println!("8. Review a Session");
+            println!("9. Back");
+            let input = self.get_user_input("Choose an option: ").await?;
+
+            let result = match input.trim() {
+                "1" => self.create_question().await,
+                "2" => self.manage_questions().await,
+                "3" => self.create_game().await,
+                "4" => self.edit_games().await,
+                "5" => self.create_player().await,
+                "6" => self.delete_players().await,
+                "7" => self.trash().await,
+                "8" => self.review_session().await,
+                "9" => break,
+                _ => {
+                    println!("Invalid option!");
+                    Ok(())
+                }
+            };
+
+            if let Err(e) = result {
+                self.report_error(e).await?;
             }
         }
         Ok(())
     }
 
-    async fn create_player(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    async fn create_player(&mut self) -> Result<()> {
         clear_screen();
-        let username = self.get_user_input("Enter username: ");
-        let password = self.get_user_input("Enter password: ");
+        let username = self.get_user_input("Enter username: ").await?;
+        let password = self.get_user_input("Enter password: ").await?;
 
         self.db
             .create_new_player(username.trim(), password.trim())
@@ -103,7 +233,7 @@ impl GameManager {
         Ok(())
     }
 
-    async fn delete_players(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    async fn delete_players(&mut self) -> Result<()> {
         clear_screen();
         let players = self.db.get_all_players().await?;
 
@@ -112,12 +242,12 @@ impl GameManager {
             println!("{}. {}", player.player_id, player.username);
         }
 
-        let input = self.get_user_input("Enter player ID to delete: ");
+        let input = self.get_user_input("Enter player ID to delete: ").await?;
 
         if let Ok(player_id) = input.trim().parse::<i32>() {
             if players.iter().any(|p| p.player_id == player_id) {
                 self.db.delete_player(player_id).await?;
-                println!("Player deleted successfully!");
+                println!("Player moved to the trash.");
             } else {
                 println!("Invalid player ID!");
             }
@@ -127,54 +257,744 @@ impl GameManager {
         Ok(())
     }
 
-    async fn player_menu(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    /// Rebuilds the order a session's questions were played in from its seed
+    /// and play order, next to the answers on record, to settle disputes.
+    async fn review_session(&self) -> Result<()> {
+        clear_screen();
+        let input = self.get_user_input("Enter session #: ").await?;
+        let session = self
+            .db
+            .get_session(parse_number(input.trim_start_matches('#'))?)
+            .await?;
+
+        let player = self
+            .db
+            .get_all_players()
+            .await?
+            .into_iter()
+            .find(|p| p.player_id == session.player_id)
+            .map(|p| p.username)
+            .unwrap_or_else(|| format!("Player {} (in the trash)", session.player_id));
+        let title = match self.db.get_game(session.game_id).await {
+            Ok(game) => game.title,
+            Err(TriviaError::NotFound(_)) => format!("Game {} (in the trash)", session.game_id),
+            Err(e) => return Err(e),
+        };
+        let mut questions = self.db.get_game_questions(session.game_id).await?;
+        session.arrange(&mut questions);
+        let answers = self.db.get_session_answers(session.session_id).await?;
+
+        clear_screen();
+        println!("\n=== Session #{} ===", session.session_id);
+        println!("Player: {}", player);
+        println!("Game: {} ({} mode)", title, session.mode);
+        match session.seed {
+            Some(seed) => println!(
+                "Play order: {} (seed {})",
+                format_play_order(session.play_order, session.draw_count),
+                seed
+            ),
+            None => println!("Play order: {}", PlayOrder::Fixed),
+        }
+        println!("Started: {}", session.started_at.format("%Y-%m-%d %H:%M"));
+        match session.ended_at {
+            Some(ended_at) => println!(
+                "Finished: {}, {} of {} correct, {}/{} points",
+                ended_at.format("%Y-%m-%d %H:%M"),
+                session.correct_answers,
+                session.total_questions,
+                format_score(session.score),
+                format_score(session.max_score)
+            ),
+            None => println!("Not finished"),
+        }
+
+        println!("\nQuestions in the order they were played:");
+        for (i, question) in questions.iter().enumerate() {
+            let answer = answers
+                .iter()
+                .find(|a| a.question_id == question.question_id);
+            let outcome = match answer {
+                Some(a) if a.is_correct => format!("'{}', correct", a.submitted_answer),
+                Some(a) if a.score > 0.0 => {
+                    format!("'{}', {:.0}% credit", a.submitted_answer, a.score * 100.0)
+                }
+                Some(a) => format!("'{}', incorrect", a.submitted_answer),
+                None => "not answered".to_string(),
+            };
+            println!(
+                "{}. [ID: {}] {} ({})",
+                i + 1,
+                question.question_id,
+                question.question_text,
+                outcome
+            );
+        }
+
+        let rebuilt: Vec<i32> = questions.iter().map(|q| q.question_id).collect();
+        let played: Vec<i32> = answers.iter().map(|a| a.question_id).collect();
+        if !rebuilt.starts_with(&played) {
+            println!(
+                "\nThe game's questions have changed since this session, so this order \
+                 differs from the answers on record."
+            );
+        }
+
+        self.get_user_input("\nPress Enter to continue...").await?;
+        Ok(())
+    }
+
+    /// Lists deleted players, games and questions for restoring or purging.
+    async fn trash(&self) -> Result<()> {
+        loop {
+            clear_screen();
+            let items = self.db.get_trash().await?;
+
+            println!("\n=== Trash ===");
+            if items.is_empty() {
+                println!("The trash is empty.");
+                self.get_user_input("\nPress Enter to continue...").await?;
+                return Ok(());
+            }
+            println!(
+                "{:>3}  {:<9} {:>4}  {:<16}  Name",
+                "#", "Type", "ID", "Deleted"
+            );
+            for (i, item) in items.iter().enumerate() {
+                let deleted_at = item
+                    .deleted_at
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                println!(
+                    "{:>3}  {:<9} {:>4}  {:<16}  {}",
+                    i + 1,
+                    item.kind.to_string(),
+                    item.id,
+                    deleted_at,
+                    item.name
+                );
+            }
+
+            println!("\nTrash Options:");
+            println!("1. Restore item");
+            println!("2. Delete item permanently");
+            println!("3. Back");
+
+            let choice = self.get_user_input("Enter your choice: ").await?;
+            let restore = match choice.trim() {
+                "1" => true,
+                "2" => false,
+                "3" => break,
+                _ => {
+                    println!("Invalid choice, please try again.");
+                    continue;
+                }
+            };
+
+            let input = self.get_user_input("Enter item # from the list: ").await?;
+            let item = usize::try_from(parse_number(&input)?)
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|i| items.get(i))
+                .ok_or_else(|| {
+                    TriviaError::InvalidInput(format!("'{}' is not in the list", input))
+                })?;
+
+            if restore {
+                self.db.restore_item(item.kind, item.id).await?;
+                println!("{} '{}' restored.", item.kind, item.name);
+            } else {
+                if item.kind == TrashKind::Game {
+                    let usage = self.db.get_game_usage(item.id).await?;
+                    println!(
+                        "This also deletes the game's list of {} question(s) and its {} recorded session(s).",
+                        usage.question_count, usage.session_count
+                    );
+                }
+                let confirm = self
+                    .get_user_input(&format!(
+                        "Permanently delete {} '{}' and everything recorded for it? (y/n): ",
+                        item.kind.to_string().to_lowercase(),
+                        item.name
+                    ))
+                    .await?;
+                if !confirm.eq_ignore_ascii_case("y") {
+                    continue;
+                }
+                self.db.purge_item(item.kind, item.id).await?;
+                println!("{} '{}' permanently deleted.", item.kind, item.name);
+            }
+            self.get_user_input("Press Enter to continue...").await?;
+        }
+
+        Ok(())
+    }
+
+    async fn player_menu(&mut self) -> Result<()> {
         if self.current_player.is_none() {
             clear_screen();
-            let username = self.get_user_input("Enter username: ");
-            let password = self.get_user_input("Enter password: ");
+            let username = self.get_user_input("Enter username: ").await?;
+            let password = self.get_user_input("Enter password: ").await?;
 
             match self.db.login_player(username.trim(), password.trim()).await {
                 Ok(player) => {
                     self.current_player = Some(player);
                     self.current_admin = None;
                 }
-                Err(_) => {
+                Err(TriviaError::Unauthorized) => {
                     println!("Invalid username or password!");
                     return Ok(());
                 }
+                Err(e) => return Err(e),
             }
         }
 
-        let games = self.db.get_games().await?;
+        loop {
+            clear_screen();
+            println!("\n=== Player Menu ===");
+            println!("1. Play Game");
+            println!("2. Play Timed Game");
+            println!("3. Survival Mode");
+            println!("4. Practice Missed Questions");
+            println!("5. Leaderboards");
+            println!("6. Back");
+            let input = self.get_user_input("Choose an option: ").await?;
+
+            let result = match input.trim() {
+                "1" => self.select_game(GameMode::Regular).await,
+                "2" => self.select_game(GameMode::Timed).await,
+                "3" => self.survival_menu().await,
+                "4" => self.practice().await,
+                "5" => self.leaderboards().await,
+                "6" => break,
+                _ => {
+                    println!("Invalid option!");
+                    Ok(())
+                }
+            };
+
+            if let Err(e) = result {
+                self.report_error(e).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn select_game(&self, mode: GameMode) -> Result<()> {
+        if let Some(game_id) = self.choose_game().await? {
+            self.play_game(game_id, mode).await?;
+        }
+        Ok(())
+    }
+
+    /// Lets the player pick a category and then a game from it. Returns
+    /// `None` if the choice was invalid.
+    async fn choose_game(&self) -> Result<Option<i32>> {
+        let categories: Vec<Tag> = self
+            .db
+            .get_tags()
+            .await?
+            .into_iter()
+            .filter(|t| t.game_count > 0)
+            .collect();
+
+        let mut category = None;
+        if !categories.is_empty() {
+            clear_screen();
+            println!("\nCategories:");
+            for (i, tag) in categories.iter().enumerate() {
+                let noun = if tag.game_count == 1 { "game" } else { "games" };
+                println!("{}. {} ({} {})", i + 1, tag.name, tag.game_count, noun);
+            }
+
+            let input = self
+                .get_user_input("Select a category (press Enter for all games): ")
+                .await?;
+            if !input.is_empty() {
+                match input.parse::<usize>() {
+                    Ok(n) if (1..=categories.len()).contains(&n) => {
+                        category = Some(&categories[n - 1]);
+                    }
+                    _ => {
+                        println!("Invalid category!");
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+
+        let games = match category {
+            Some(tag) => self.db.get_games_by_tag(&tag.name).await?,
+            None => self.db.get_games().await?,
+        };
 
         clear_screen();
-        println!("\nAvailable Games:");
+        match category {
+            Some(tag) => println!("\nGames in '{}':", tag.name),
+            None => println!("\nAvailable Games:"),
+        }
         for game in &games {
             println!("{}. {}", game.game_id, game.title);
         }
 
-        let input = self.get_user_input("Select a game (enter game ID): ");
+        let input = self
+            .get_user_input("Select a game (enter game ID): ")
+            .await?;
 
         if let Ok(game_id) = input.trim().parse::<i32>() {
-            self.play_regular_game(game_id).await?;
+            if !games.iter().any(|g| g.game_id == game_id) {
+                return Err(TriviaError::NotFound(format!("Game {}", game_id)));
+            }
+            Ok(Some(game_id))
         } else {
             println!("Invalid game ID!");
+            Ok(None)
+        }
+    }
+
+    /// Lists every game and reads the ID of one.
+    async fn pick_game(&self) -> Result<Game> {
+        let games = self.db.get_games().await?;
+        println!("\nGames:");
+        for game in &games {
+            println!("{}. {}", game.game_id, game.title);
+        }
+        let game_id = parse_number(
+            &self
+                .get_user_input("Select a game (enter game ID): ")
+                .await?,
+        )?;
+        games
+            .into_iter()
+            .find(|g| g.game_id == game_id)
+            .ok_or_else(|| TriviaError::NotFound(format!("Game {}", game_id)))
+    }
+
+    async fn survival_menu(&self) -> Result<()> {
+        loop {
+            clear_screen();
+            println!("\n=== Survival Mode ===");
+            println!(
+                "Answer until you run out of lives. You have {}.",
+                format_lives(self.survival_lives())
+            );
+            println!("1. Play one game");
+            println!("2. Play every game, shuffled");
+            println!("3. High scores");
+            println!("4. Back");
+            let input = self.get_user_input("Choose an option: ").await?;
+
+            let result = match input.trim() {
+                "1" => self.select_survival_game().await,
+                "2" => self.play_survival(None).await,
+                "3" => self.survival_high_scores().await,
+                "4" => break,
+                _ => {
+                    println!("Invalid option!");
+                    Ok(())
+                }
+            };
+
+            if let Err(e) = result {
+                self.report_error(e).await?;
+            }
         }
         Ok(())
     }
 
-    async fn create_question(&self) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(admin) = self.current_admin.clone() {
-            let text = self.get_user_input("Enter question text: ");
+    async fn select_survival_game(&self) -> Result<()> {
+        if let Some(game_id) = self.choose_game().await? {
+            self.play_survival(Some(game_id)).await?;
+        }
+        Ok(())
+    }
 
-            let answer = self.get_user_input("Enter correct answer (true/false): ");
+    /// Wrong answers a survival run allows.
+    fn survival_lives(&self) -> i32 {
+        self.settings.survival_lives.max(1)
+    }
 
-            let answer = answer.trim().to_lowercase() == "true";
+    /// Every question used by a game, once each, in random order.
+    async fn question_pool(&self) -> Result<Vec<GameQuestionFull>> {
+        let mut pool: Vec<GameQuestionFull> = Vec::new();
+        for game in self.db.get_games().await? {
+            for question in self.db.get_game_questions(game.game_id).await? {
+                if !pool.iter().any(|q| q.question_id == question.question_id) {
+                    pool.push(question);
+                }
+            }
+        }
+        pool.shuffle(&mut rand::thread_rng());
+        Ok(pool)
+    }
 
-            self.db
-                .create_question(text.trim(), answer, admin.admin_id)
+    /// Plays a game's questions in order, or the shuffled pool of every game
+    /// if `game_id` is `None`, until the player runs out of lives.
+    async fn play_survival(&self, game_id: Option<i32>) -> Result<()> {
+        let player_id = self
+            .current_player
+            .as_ref()
+            .map(|p| p.player_id)
+            .ok_or(TriviaError::Unauthorized)?;
+
+        let questions = match game_id {
+            Some(game_id) => {
+                let game = self.db.get_game(game_id).await?;
+                let mut questions = self.db.get_game_questions(game_id).await?;
+                game.play_order
+                    .arrange(&mut questions, game.draw_count, rand::random());
+                questions
+            }
+            None => self.question_pool().await?,
+        };
+        if questions.is_empty() {
+            println!("There are no questions to play.");
+            self.get_user_input("Press Enter to continue...").await?;
+            return Ok(());
+        }
+
+        let lives = self.survival_lives();
+        let mut lives_left = lives;
+        let mut questions_answered = 0;
+        let mut correct_answers = 0;
+        let mut score = 0.0;
+
+        for (i, question) in questions.iter().enumerate() {
+            let key = self.answer_key(question).await?;
+
+            clear_screen();
+            let points = question.difficulty.points();
+            println!(
+                "\nQuestion {} ({}, {}) - {} left",
+                i + 1,
+                question.difficulty,
+                format_points(points),
+                format_lives(lives_left)
+            );
+            println!("{}", question.question_text);
+
+            let input = self.read_response(&key, None).await?.unwrap_or_default();
+            questions_answered += 1;
+
+            let credit = key.score(&input);
+            if credit == 1.0 {
+                correct_answers += 1;
+                score += points;
+                println!("Correct! +{}", format_score(points));
+            } else {
+                lives_left -= 1;
+                if credit > 0.0 {
+                    println!(
+                        "Only partly correct, which costs a life. Correct answer is: {}",
+                        key
+                    );
+                } else {
+                    println!("Incorrect! Correct answer is: {}", key);
+                }
+            }
+            print_explanation(question);
+
+            if lives_left == 0 || i + 1 == questions.len() {
+                self.get_user_input("\nPress Enter to see your results...")
+                    .await?;
+                break;
+            }
+            self.get_user_input("\nPress Enter for the next question...")
                 .await?;
-            println!("Question created successfully!");
+        }
+
+        self.db
+            .record_survival_run(
+                player_id,
+                game_id,
+                lives,
+                questions_answered,
+                correct_answers,
+                score,
+            )
+            .await?;
+
+        clear_screen();
+        if lives_left > 0 {
+            println!(
+                "\nYou survived every question with {} to spare!\n\n",
+                format_lives(lives_left)
+            );
+        } else {
+            println!("\nOut of lives!\n\n");
+        }
+        println!(
+            "You answered {} of {} questions correctly.",
+            correct_answers, questions_answered
+        );
+        println!("You scored {}.", format_points(score));
+
+        let high_scores = self
+            .db
+            .get_survival_high_scores(game_id, lives, i32::MAX)
+            .await?;
+        if let Some(entry) = high_scores.iter().find(|e| e.player_id == player_id) {
+            println!(
+                "\nYour best run ranks #{} of {} player(s).",
+                entry.rank,
+                high_scores.len()
+            );
+        }
+
+        self.get_user_input("\nPress Enter to continue...").await?;
+        Ok(())
+    }
+
+    async fn survival_high_scores(&self) -> Result<()> {
+        clear_screen();
+        println!("\n=== Survival High Scores ===");
+        println!("1. One game");
+        println!("2. Every game, shuffled");
+        let scope = self.get_user_input("Choose an option: ").await?;
+
+        let game = match scope.trim() {
+            "1" => Some(self.pick_game().await?),
+            "2" => None,
+            _ => {
+                println!("Invalid option!");
+                return Ok(());
+            }
+        };
+
+        let lives = self.survival_lives();
+        let entries = self
+            .db
+            .get_survival_high_scores(game.as_ref().map(|g| g.game_id), lives, LEADERBOARD_SIZE)
+            .await?;
+
+        clear_screen();
+        let title = game.as_ref().map_or("Every Game", |g| g.title.as_str());
+        println!(
+            "\n=== {} Survival High Scores ({}) ===",
+            title,
+            format_lives(lives)
+        );
+        if entries.is_empty() {
+            println!("No survival runs yet.");
+        } else {
+            println!(
+                "{:>4}  {:<20} {:>9} {:>7}  {:<10}",
+                "Rank", "Player", "Correct", "Points", "Played"
+            );
+            for entry in &entries {
+                let highlight = self
+                    .current_player
+                    .as_ref()
+                    .is_some_and(|p| p.player_id == entry.player_id);
+                println!(
+                    "{:>4}  {:<20} {:>9} {:>7}  {}{}",
+                    entry.rank,
+                    entry.username,
+                    format!("{}/{}", entry.correct_answers, entry.questions_answered),
+                    format_score(entry.score),
+                    entry.played_at.format("%Y-%m-%d"),
+                    if highlight { "  <- you" } else { "" }
+                );
+            }
+        }
+
+        self.get_user_input("\nPress Enter to continue...").await?;
+        Ok(())
+    }
+
+    /// Plays a round of the questions the player has missed, due first, and
+    /// reschedules each one with SM-2. Nothing is recorded as a game session,
+    /// so practice never shows up on the leaderboards.
+    async fn practice(&self) -> Result<()> {
+        let player_id = self
+            .current_player
+            .as_ref()
+            .map(|p| p.player_id)
+            .ok_or(TriviaError::Unauthorized)?;
+
+        let questions = self
+            .db
+            .get_practice_questions(player_id, Utc::now(), PRACTICE_ROUND_SIZE)
+            .await?;
+        if questions.is_empty() {
+            clear_screen();
+            println!("\nNothing to practice right now.");
+            match self.db.get_next_practice_due(player_id).await? {
+                Some(due_at) => println!(
+                    "Your next review is due {}.",
+                    due_at.format("%Y-%m-%d %H:%M")
+                ),
+                None => println!("Questions you miss in games will show up here."),
+            }
+            self.get_user_input("\nPress Enter to continue...").await?;
+            return Ok(());
+        }
+
+        let mut correct_answers = 0;
+        for (i, question) in questions.iter().enumerate() {
+            let key = self.answer_key(question).await?;
+
+            clear_screen();
+            let asked_at = Instant::now();
+            println!(
+                "\nPractice {} of {} ({})",
+                i + 1,
+                questions.len(),
+                question.difficulty
+            );
+            println!("{}", question.question_text);
+
+            let input = self.read_response(&key, None).await?.unwrap_or_default();
+            let credit = key.score(&input);
+            if credit == 1.0 {
+                correct_answers += 1;
+                println!("Correct!");
+            } else if credit > 0.0 {
+                println!(
+                    "Partly correct ({:.0}% credit). Correct answer is: {}",
+                    credit * 100.0,
+                    key
+                );
+            } else {
+                println!("Incorrect! Correct answer is: {}", key);
+            }
+            print_explanation(question);
+
+            let now = Utc::now();
+            let mut card = self
+                .db
+                .get_practice_card(player_id, question.question_id)
+                .await?
+                .unwrap_or_else(|| PracticeCard::new(player_id, question.question_id, now));
+            // A card queued before it is due was missed in a game since.
+            if card.due_at > now {
+                card.restart();
+            }
+            card.review(PracticeCard::quality(credit, asked_at.elapsed()), now);
+            self.db.save_practice_card(&card).await?;
+            println!("Next review in {}.", format_days(card.interval_days));
+
+            if i + 1 == questions.len() {
+                self.get_user_input("\nPress Enter to see your results...")
+                    .await?;
+            } else {
+                self.get_user_input("\nPress Enter for the next question...")
+                    .await?;
+            }
+        }
+
+        clear_screen();
+        println!("\nPractice complete!\n\n");
+        println!(
+            "You answered {} of {} questions correctly.",
+            correct_answers,
+            questions.len()
+        );
+        self.get_user_input("\nPress Enter to continue...").await?;
+        Ok(())
+    }
+
+    async fn leaderboards(&self) -> Result<()> {
+        clear_screen();
+        println!("\n=== Leaderboards ===");
+        println!("1. Per-game leaderboard");
+        println!("2. Global leaderboard");
+        let scope = self.get_user_input("Choose an option: ").await?;
+
+        let game = match scope.trim() {
+            "1" => Some(self.pick_game().await?),
+            "2" => None,
+            _ => {
+                println!("Invalid option!");
+                return Ok(());
+            }
+        };
+
+        let mode = match self
+            .get_user_input("Show (1) regular or (2) timed games: ")
+            .await?
+            .trim()
+        {
+            "2" => GameMode::Timed,
+            _ => GameMode::Regular,
+        };
+
+        let basis = match self
+            .get_user_input("Rank by (1) best attempt or (2) latest attempt: ")
+            .await?
+            .trim()
+        {
+            "2" => LeaderboardBasis::Latest,
+            _ => LeaderboardBasis::Best,
+        };
+
+        let entries = match &game {
+            Some(game) => {
+                self.db
+                    .get_game_leaderboard(game.game_id, mode, basis, LEADERBOARD_SIZE)
+                    .await?
+            }
+            None => {
+                self.db
+                    .get_global_leaderboard(mode, basis, LEADERBOARD_SIZE)
+                    .await?
+            }
+        };
+
+        clear_screen();
+        match &game {
+            Some(game) => println!("\n=== {} {} Leaderboard ===", game.title, mode),
+            None => println!("\n=== Global {} Leaderboard ===", mode),
+        }
+        if entries.is_empty() {
+            println!("No finished games yet.");
+        } else {
+            // Timed boards rank by score plus speed bonus, so show the bonus.
+            let timed = mode == GameMode::Timed;
+            println!(
+                "{:>4}  {:<20} {:>9} {:>7} {:>6} {:>9}",
+                "Rank",
+                "Player",
+                "Score",
+                if timed { "Bonus" } else { "" },
+                "Games",
+                "Time"
+            );
+            for entry in &entries {
+                let highlight = self
+                    .current_player
+                    .as_ref()
+                    .is_some_and(|p| p.player_id == entry.player_id);
+                println!(
+                    "{:>4}  {:<20} {:>9} {:>7} {:>6} {:>8.1}s{}",
+                    entry.rank,
+                    entry.username,
+                    format!(
+                        "{}/{}",
+                        format_score(entry.score),
+                        format_score(entry.max_score)
+                    ),
+                    if timed {
+                        format!("+{}", format_score(entry.bonus))
+                    } else {
+                        String::new()
+                    },
+                    entry.games_played,
+                    entry.time_ms as f64 / 1000.0,
+                    if highlight { "  <- you" } else { "" }
+                );
+            }
+        }
+
+        self.get_user_input("\nPress Enter to continue...").await?;
+        Ok(())
+    }
+
+    async fn create_question(&self) -> Result<()> {
+        if self.current_admin.is_some() {
+            if self.create_question_inner(false).await?.is_some() {
+                println!("Question created successfully!");
+            }
             Ok(())
         } else {
             println!("Not a valid admin account.");
@@ -182,12 +1002,13 @@ impl GameManager {
         }
     }
 
-    async fn create_game(&self) -> Result<(), Box<dyn std::error::Error>> {
+    async fn create_game(&self) -> Result<()> {
         if let Some(admin) = self.current_admin.clone() {
-            let title = self.get_user_input("Enter game title: ");
+            let title = self.get_user_input("Enter game title: ").await?;
 
-            let description =
-                self.get_user_input("Enter game description (optional, press Enter to skip): ");
+            let description = self
+                .get_user_input("Enter game description (optional, press Enter to skip): ")
+                .await?;
 
             let description = if description.trim().is_empty() {
                 None
@@ -195,9 +1016,15 @@ impl GameManager {
                 Some(description.trim())
             };
 
+            let tags = parse_tags(
+                &self
+                    .get_user_input("Categories, comma-separated (optional): ")
+                    .await?,
+            );
+
             let game_id = self
                 .db
-                .create_game(title.trim(), description, admin.admin_id)
+                .create_game_with_tags(title.trim(), description, &tags, admin.admin_id)
                 .await?;
             println!("Game created! Now let's add questions.");
 
@@ -206,12 +1033,13 @@ impl GameManager {
                 println!("1. Create new question");
                 println!("2. Add existing question");
                 println!("3. Finish adding questions");
-                let input = self.get_user_input("Choose an option: ");
+                let input = self.get_user_input("Choose an option: ").await?;
 
                 match input.trim() {
                     "1" => {
-                        let question_id = self.create_question_inner().await?;
-                        self.add_question_to_game(game_id, question_id).await?;
+                        if let Some(question_id) = self.create_question_inner(true).await? {
+                            self.add_question_to_game(game_id, question_id).await?;
+                        }
                     }
                     "2" => {
                         self.add_existing_question(game_id).await?;
@@ -229,37 +1057,558 @@ impl GameManager {
         }
     }
 
-    async fn create_question_inner(&self) -> Result<i32, Box<dyn std::error::Error>> {
-        if let Some(admin) = self.current_admin.clone() {
-            let text = self.get_user_input("Enter question text: ");
+    /// Prompts for a new question and stores it, returning its ID.
+    ///
+    /// If the bank already has a question like it, the admin is shown the
+    /// matches first. With `reuse`, they can pick one of those to use instead,
+    /// and its ID is returned; otherwise they can back out, giving `None`.
+    async fn create_question_inner(&self, reuse: bool) -> Result<Option<i32>> {
+        if let Some(admin) = self.current_admin.clone() {
+            let text = self.get_user_input("Enter question text: ").await?;
+
+            let similar = duplicates::find_similar(&text, &self.db.get_all_questions().await?);
+            if !similar.is_empty() {
+                println!("\nThe question bank already has similar questions:");
+                for found in &similar {
+                    let likeness = if found.exact {
+                        "same text".to_string()
+                    } else {
+                        format!("{:.0}% alike", found.similarity * 100.0)
+                    };
+                    println!(
+                        "{}. {} ({}, {})",
+                        found.question.question_id,
+                        found.question.question_text,
+                        found.question.kind,
+                        likeness
+                    );
+                }
+
+                if reuse {
+                    let input = self.get_user_input(
+                        "Enter a question ID to use it instead, or press Enter to create a new one: ",
+                    ).await?;
+                    if !input.is_empty() {
+                        let question_id = parse_number(&input)?;
+                        if !similar
+                            .iter()
+                            .any(|f| f.question.question_id == question_id)
+                        {
+                            return Err(TriviaError::NotFound(format!(
+                                "Question {} among the similar questions",
+                                question_id
+                            )));
+                        }
+                        return Ok(Some(question_id));
+                    }
+                } else {
+                    let confirm = self.get_user_input("Create it anyway? (y/n): ").await?;
+                    if !confirm.eq_ignore_ascii_case("y") {
+                        println!("Question not created.");
+                        return Ok(None);
+                    }
+                }
+            }
+
+            println!("Question type:");
+            print_kind_menu();
+            let kind = parse_kind(&self.get_user_input("Choose a type: ").await?)?;
+            let answer = self.read_answer(kind).await?;
+            println!("Difficulty:");
+            print_difficulty_menu();
+            let difficulty = match self
+                .get_user_input("Choose a difficulty (press Enter for medium): ")
+                .await?
+                .as_str()
+            {
+                "" => Difficulty::default(),
+                input => parse_difficulty(input)?,
+            };
+            let time_limit = parse_time_limit(
+                &self
+                    .get_user_input(&format!(
+                "Time limit in timed mode, in seconds (press Enter for the default of {}): ",
+                self.settings.time_limit_secs
+            ))
+                    .await?,
+            )?;
+
+            let explanation = self
+                .get_user_input("Explanation shown after answering (optional): ")
+                .await?;
+            let source = self.get_user_input("Source reference (optional): ").await?;
+            let tags = parse_tags(
+                &self
+                    .get_user_input("Tags, comma-separated (optional): ")
+                    .await?,
+            );
+
+            let question = NewQuestion {
+                text,
+                answer,
+                difficulty,
+                time_limit_secs: time_limit,
+                explanation: (!explanation.is_empty()).then_some(explanation),
+                source: (!source.is_empty()).then_some(source),
+                tags,
+            };
+            let question_id = self
+                .db
+                .create_question_with_details(&question, admin.admin_id)
+                .await?;
+
+            Ok(Some(question_id))
+        } else {
+            Err(TriviaError::Unauthorized)
+        }
+    }
+
+    /// Prompts for the answer to a question of the given kind.
+    async fn read_answer(&self, kind: QuestionKind) -> Result<AnswerSpec> {
+        Ok(match kind {
+            QuestionKind::TrueFalse => {
+                let answer = self
+                    .get_user_input("Enter correct answer (true/false): ")
+                    .await?;
+
+                AnswerSpec::TrueFalse(answer.trim().to_lowercase() == "true")
+            }
+            QuestionKind::MultipleChoice => {
+                let count = parse_number(
+                    &self
+                        .get_user_input(&format!(
+                            "Number of choices ({}-{}): ",
+                            MIN_CHOICES, MAX_CHOICES
+                        ))
+                        .await?,
+                )?;
+                let count = usize::try_from(count).unwrap_or(0);
+                if !(MIN_CHOICES..=MAX_CHOICES).contains(&count) {
+                    return Err(TriviaError::InvalidInput(format!(
+                        "choose between {} and {} choices",
+                        MIN_CHOICES, MAX_CHOICES
+                    )));
+                }
+
+                let mut choices = Vec::with_capacity(count);
+                for i in 0..count {
+                    choices.push(
+                        self.get_user_input(&format!("Choice {}: ", choice_label(i)))
+                            .await?,
+                    );
+                }
+
+                let input = self.get_user_input("Correct choice (letter): ").await?;
+                let correct_choice = parse_choice(&input, count).ok_or_else(|| {
+                    TriviaError::InvalidInput(format!("'{}' is not one of the choices", input))
+                })?;
+
+                AnswerSpec::MultipleChoice {
+                    choices,
+                    correct_choice,
+                }
+            }
+            QuestionKind::FreeText => {
+                println!("Enter each accepted answer; leave blank to finish.");
+                let mut accepted = Vec::new();
+                loop {
+                    let answer = self.get_user_input("Accepted answer: ").await?;
+                    if answer.is_empty() {
+                        break;
+                    }
+                    accepted.push(answer);
+                }
+
+                let input = self
+                    .get_user_input("Typos to forgive per answer (default 0): ")
+                    .await?;
+                let max_distance = if input.is_empty() {
+                    0
+                } else {
+                    parse_number(&input)?
+                };
+
+                AnswerSpec::FreeText {
+                    accepted,
+                    max_distance,
+                }
+            }
+            QuestionKind::Numeric => {
+                let input = self.get_user_input("Correct value: ").await?;
+                let target_value = matching::parse_number(&input).ok_or_else(|| {
+                    TriviaError::InvalidInput(format!("'{}' is not a number", input))
+                })?;
+
+                let unit = self.get_user_input("Unit (optional, e.g. km): ").await?;
+                let unit = (!unit.is_empty()).then_some(unit);
+
+                let input = self
+                    .get_user_input("Tolerance (e.g. 5 or 2%, press Enter for exact): ")
+                    .await?;
+                let (tolerance, tolerance_percent) = parse_tolerance(&input)?;
+
+                AnswerSpec::Numeric {
+                    target_value,
+                    tolerance,
+                    tolerance_percent,
+                    unit,
+                }
+            }
+            QuestionKind::Ordering => {
+                println!(
+                    "Enter {} to {} items in their correct order; leave blank to finish.",
+                    MIN_ORDERING_ITEMS, MAX_ORDERING_ITEMS
+                );
+                let mut items = Vec::new();
+                while items.len() < MAX_ORDERING_ITEMS {
+                    let item = self
+                        .get_user_input(&format!("Item {}: ", items.len() + 1))
+                        .await?;
+                    if item.is_empty() {
+                        break;
+                    }
+                    items.push(item);
+                }
+
+                let partial_credit = self
+                    .get_user_input("Give partial credit for partly correct orders? (y/n): ")
+                    .await?
+                    .eq_ignore_ascii_case("y");
+
+                AnswerSpec::Ordering {
+                    items,
+                    partial_credit,
+                }
+            }
+        })
+    }
+
+    async fn manage_questions(&self) -> Result<()> {
+        let mut search = QuestionSearch::default();
+
+        loop {
+            clear_screen();
+            let mut questions = self.db.get_question_usage().await?;
+            if !search.is_empty() {
+                let found = self.db.search_questions(&search, SEARCH_LIMIT).await?;
+                questions = found
+                    .iter()
+                    .filter_map(|f| questions.iter().find(|q| q.question_id == f.question_id))
+                    .cloned()
+                    .collect();
+                println!("\n=== Question Bank (search results) ===");
+            } else {
+                println!("\n=== Question Bank ===");
+            }
+            println!(
+                "{:>4}  {:<16} {:<10} {:>5} {:>7}  Question",
+                "ID", "Type", "Difficulty", "Games", "Answers"
+            );
+            for question in &questions {
+                println!(
+                    "{:>4}  {:<16} {:<10} {:>5} {:>7}  {}",
+                    question.question_id,
+                    question.kind.to_string(),
+                    question.difficulty.to_string(),
+                    question.game_count,
+                    question.answer_count,
+                    question.question_text
+                );
+            }
+
+            println!("\nManage Questions Options:");
+            println!("1. Edit question");
+            println!("2. Delete question");
+            println!("3. Search");
+            println!("4. Duplicate report");
+            println!("5. Back");
+
+            let choice = self.get_user_input("Enter your choice: ").await?;
+
+            match choice.trim() {
+                "1" => {
+                    let input = self.get_user_input("Enter question ID to edit: ").await?;
+                    self.edit_question(parse_number(&input)?).await?;
+                }
+                "2" => {
+                    let input = self.get_user_input("Enter question ID to delete: ").await?;
+                    let question_id = parse_number(&input)?;
+                    let question = questions
+                        .iter()
+                        .find(|q| q.question_id == question_id)
+                        .ok_or_else(|| {
+                            TriviaError::NotFound(format!("Question {}", question_id))
+                        })?;
+                    self.delete_question(question).await?;
+                }
+                "3" => {
+                    println!("Leave every filter blank to show all questions again.");
+                    search = self.read_question_search().await?;
+                }
+                "4" => self.duplicate_report().await?,
+                "5" => break,
+                _ => println!("Invalid choice, please try again."),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists groups of questions across the bank that look like duplicates
+    /// of each other.
+    async fn duplicate_report(&self) -> Result<()> {
+        let clusters = duplicates::find_clusters(&self.db.get_all_questions().await?);
+
+        clear_screen();
+        println!("\n=== Suspected Duplicates ===");
+        if clusters.is_empty() {
+            println!("No duplicate questions found.");
+        }
+        for (i, cluster) in clusters.iter().enumerate() {
+            println!("\nGroup {}:", i + 1);
+            for question in cluster {
+                println!(
+                    "{:>4}  {:<16} {}",
+                    question.question_id,
+                    question.kind.to_string(),
+                    question.question_text
+                );
+            }
+        }
+
+        self.get_user_input("\nPress Enter to continue...").await?;
+        Ok(())
+    }
+
+    /// Moves a question to the trash once the admin confirms, after warning
+    /// about the games that still use it.
+    async fn delete_question(&self, question: &QuestionUsage) -> Result<()> {
+        let question_id = question.question_id;
+        let games = self.db.get_games_using_question(question_id).await?;
+
+        println!("\nQuestion {}: {}", question_id, question.question_text);
+        if games.is_empty() {
+            println!("No game uses this question.");
+        } else {
+            println!("This question is still used by {} game(s):", games.len());
+            for game in &games {
+                println!("  - {}", game.title);
+            }
+            println!("It will be hidden from those games until it is restored from the trash.");
+        }
+
+        let confirm = self
+            .get_user_input("Move this question to the trash? (y/n): ")
+            .await?;
+        if confirm.eq_ignore_ascii_case("y") {
+            self.db.delete_question(question_id).await?;
+            println!("Question moved to the trash.");
+        } else {
+            println!("Delete cancelled.");
+        }
+
+        self.get_user_input("Press Enter to continue...").await?;
+        Ok(())
+    }
+
+    async fn edit_question(&self, question_id: i32) -> Result<()> {
+        let Some(admin) = self.current_admin.clone() else {
+            return Err(TriviaError::Unauthorized);
+        };
+
+        loop {
+            clear_screen();
+            let question = self.db.get_question(question_id).await?;
+            let answer = self.db.get_answer_spec(&question).await?;
+
+            println!("\n=== Question {} ({}) ===", question_id, question.kind);
+            println!("Text: {}", question.question_text);
+            println!("Answer: {}", answer);
+            println!(
+                "Difficulty: {} ({})",
+                question.difficulty,
+                format_points(question.difficulty.points())
+            );
+            match question.time_limit_secs {
+                Some(seconds) => println!("Time limit: {}s", seconds),
+                None => println!("Time limit: {}s (default)", self.settings.time_limit_secs),
+            }
+            println!(
+                "Explanation: {}",
+                question.explanation.as_deref().unwrap_or("(none)")
+            );
+            println!("Source: {}", question.source.as_deref().unwrap_or("(none)"));
+            let tags = self.db.get_question_tags(question_id).await?;
+            println!("Tags: {}", format_tags(&tags));
+
+            println!("\nEdit Question Options:");
+            println!("1. Edit text");
+            println!("2. Edit answer");
+            println!("3. Edit explanation and source");
+            println!("4. Edit tags");
+            println!("5. Edit difficulty");
+            println!("6. Edit time limit");
+            println!("7. View history");
+            println!("8. Revert to a revision");
+            println!("9. Back");
+
+            let choice = self.get_user_input("Enter your choice: ").await?;
+
+            let (mut text, mut explanation, mut source) = (
+                question.question_text,
+                question.explanation,
+                question.source,
+            );
+            let mut new_answer = answer;
+            match choice.trim() {
+                "1" => text = self.get_user_input("New question text: ").await?,
+                "2" => new_answer = self.read_answer(question.kind).await?,
+                "3" => {
+                    let input = self
+                        .get_user_input("New explanation (blank to clear): ")
+                        .await?;
+                    explanation = (!input.is_empty()).then_some(input);
+                    let input = self.get_user_input("New source (blank to clear): ").await?;
+                    source = (!input.is_empty()).then_some(input);
+                }
+                "4" => {
+                    let input = self
+                        .get_user_input("New tags, comma-separated (blank to clear): ")
+                        .await?;
+                    self.db
+                        .set_question_tags(question_id, &parse_tags(&input))
+                        .await?;
+                    println!("Tags updated successfully!");
+                    self.get_user_input("Press Enter to continue...").await?;
+                    continue;
+                }
+                "5" => {
+                    print_difficulty_menu();
+                    let input = self.get_user_input("New difficulty: ").await?;
+                    self.db
+                        .set_question_difficulty(question_id, parse_difficulty(&input)?)
+                        .await?;
+                    println!("Difficulty updated successfully!");
+                    self.get_user_input("Press Enter to continue...").await?;
+                    continue;
+                }
+                "6" => {
+                    let input = self
+                        .get_user_input("New time limit in seconds (blank for the default): ")
+                        .await?;
+                    self.db
+                        .set_question_time_limit(question_id, parse_time_limit(&input)?)
+                        .await?;
+                    println!("Time limit updated successfully!");
+                    self.get_user_input("Press Enter to continue...").await?;
+                    continue;
+                }
+                "7" => {
+                    self.show_question_history(question_id).await?;
+                    continue;
+                }
+                "8" => {
+                    self.revert_question(question_id).await?;
+                    continue;
+                }
+                "9" => break,
+                _ => {
+                    println!("Invalid choice, please try again.");
+                    continue;
+                }
+            }
+
+            self.db
+                .update_question(
+                    question_id,
+                    text.trim(),
+                    &new_answer,
+                    explanation.as_deref(),
+                    source.as_deref(),
+                    admin.admin_id,
+                )
+                .await?;
+            println!("Question updated successfully!");
+            self.get_user_input("Press Enter to continue...").await?;
+        }
+
+        Ok(())
+    }
+
+    /// Lists a question's earlier versions, newest first.
+    async fn show_question_history(&self, question_id: i32) -> Result<()> {
+        let revisions = self.db.get_question_revisions(question_id).await?;
 
-            let answer = self.get_user_input("Enter correct answer (true/false): ");
+        clear_screen();
+        println!("\n=== History of Question {} ===", question_id);
+        if revisions.is_empty() {
+            println!("This question has never been edited.");
+        }
+        for revision in &revisions {
+            let edited_at = revision
+                .edited_at
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            println!(
+                "\nRevision {} - replaced {} by {}",
+                revision.revision_id,
+                edited_at,
+                revision.editor.as_deref().unwrap_or("(deleted admin)")
+            );
+            println!("  Text: {}", revision.question_text);
+            match AnswerSpec::from_json(&revision.answer) {
+                Ok(answer) => println!("  Answer: {}", answer),
+                Err(e) => println!("  Answer: {}", e),
+            }
+            if let Some(explanation) = &revision.explanation {
+                println!("  Explanation: {}", explanation);
+            }
+            if let Some(source) = &revision.source {
+                println!("  Source: {}", source);
+            }
+        }
 
-            let answer = answer.trim().to_lowercase() == "true";
+        self.get_user_input("\nPress Enter to continue...").await?;
+        Ok(())
+    }
 
-            self.db
-                .create_question(text.trim(), answer, admin.admin_id)
-                .await
-                .map_err(|e| e.into())
+    async fn revert_question(&self, question_id: i32) -> Result<()> {
+        let Some(admin) = self.current_admin.clone() else {
+            return Err(TriviaError::Unauthorized);
+        };
+
+        let input = self
+            .get_user_input("Revision to restore (see View history): ")
+            .await?;
+        let revision_id = parse_number(&input)?;
+
+        let confirm = self.get_user_input(&format!(
+            "Restore question {} to revision {}? The current version is kept in the history. (y/n): ",
+            question_id, revision_id
+        )).await?;
+        if !confirm.eq_ignore_ascii_case("y") {
+            println!("Revert cancelled.");
         } else {
-            println!("Not a valid admin account.");
-            Err("Not a valid admin account.".into())
+            self.db
+                .revert_question(question_id, revision_id, admin.admin_id)
+                .await?;
+            println!("Question reverted successfully!");
         }
+
+        self.get_user_input("Press Enter to continue...").await?;
+        Ok(())
     }
 
-    async fn add_question_to_game(
-        &self,
-        game_id: i32,
-        question_id: i32,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let order_input =
-            self.get_user_input("Enter question order (press Enter for next available): ");
+    async fn add_question_to_game(&self, game_id: i32, question_id: i32) -> Result<()> {
+        let order_input = self
+            .get_user_input("Enter question order (press Enter for next available): ")
+            .await?;
 
         let order = if order_input.trim().is_empty() {
             let max_order = self.db.get_max_question_order(game_id).await?;
             max_order + 1
         } else {
-            order_input.trim().parse()?
+            parse_number(&order_input)?
         };
 
         self.db
@@ -269,10 +1618,7 @@ impl GameManager {
         Ok(())
     }
 
-    async fn remove_question_from_game(
-        &self,
-        game_id: i32,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    async fn remove_question_from_game(&self, game_id: i32) -> Result<()> {
         clear_screen();
         let questions = self.db.get_game_questions(game_id).await?;
 
@@ -284,24 +1630,100 @@ impl GameManager {
             );
         }
 
-        let input = self.get_user_input("Enter question id to remove: ");
+        let input = self.get_user_input("Enter question id to remove: ").await?;
 
         self.db
-            .delete_game_question(game_id, input.trim().parse()?)
+            .delete_game_question(game_id, parse_number(&input)?)
             .await?;
         println!("Question removed from game successfully!");
         Ok(())
     }
 
-    async fn add_existing_question(&self, game_id: i32) -> Result<(), Box<dyn std::error::Error>> {
-        let questions = self.db.get_all_questions().await?;
+    /// Prompts for question bank search filters; each one can be skipped.
+    async fn read_question_search(&self) -> Result<QuestionSearch> {
+        let optional = |input: String| (!input.is_empty()).then_some(input);
+
+        let keyword = optional(
+            self.get_user_input("Keywords (press Enter to skip): ")
+                .await?,
+        );
+
+        let tags: Vec<String> = self
+            .db
+            .get_tags()
+            .await?
+            .into_iter()
+            .filter(|t| t.question_count > 0)
+            .map(|t| t.name)
+            .collect();
+        let tag = if tags.is_empty() {
+            None
+        } else {
+            println!("Tags: {}", tags.join(", "));
+            optional(self.get_user_input("Tag (press Enter to skip): ").await?)
+        };
+
+        let author = optional(
+            self.get_user_input("Created by (username, press Enter to skip): ")
+                .await?,
+        );
+
+        print_kind_menu();
+        let kind = match self
+            .get_user_input("Type (press Enter for any): ")
+            .await?
+            .as_str()
+        {
+            "" => None,
+            input => Some(parse_kind(input)?),
+        };
+
+        let created_from = optional(
+            self.get_user_input("Created on or after (YYYY-MM-DD, press Enter to skip): ")
+                .await?,
+        )
+        .map(|input| parse_date(&input))
+        .transpose()?;
+        let created_to = optional(
+            self.get_user_input("Created on or before (YYYY-MM-DD, press Enter to skip): ")
+                .await?,
+        )
+        .map(|input| parse_date(&input))
+        .transpose()?;
+
+        Ok(QuestionSearch {
+            keyword,
+            tag,
+            author,
+            kind,
+            created_from,
+            created_to,
+        })
+    }
+
+    async fn add_existing_question(&self, game_id: i32) -> Result<()> {
+        println!("\nSearch for a question to add.");
+        let search = self.read_question_search().await?;
+        let questions = if search.is_empty() {
+            self.db.get_all_questions().await?
+        } else {
+            self.db.search_questions(&search, SEARCH_LIMIT).await?
+        };
+
+        if questions.is_empty() {
+            println!("No questions match that search.");
+            return Ok(());
+        }
 
         println!("\nAvailable questions:");
         for question in &questions {
-            println!("{}. {}", question.question_id, question.question_text);
+            println!(
+                "{}. {} ({}, {})",
+                question.question_id, question.question_text, question.kind, question.difficulty
+            );
         }
 
-        let input = self.get_user_input("Enter question ID to add: ");
+        let input = self.get_user_input("Enter question ID to add: ").await?;
 
         if let Ok(question_id) = input.trim().parse::<i32>() {
             if questions.iter().any(|q| q.question_id == question_id) {
@@ -315,7 +1737,7 @@ impl GameManager {
         Ok(())
     }
 
-    async fn edit_games(&self) -> Result<(), Box<dyn std::error::Error>> {
+    async fn edit_games(&self) -> Result<()> {
         let games = self.db.get_games().await?;
 
         clear_screen();
@@ -325,7 +1747,9 @@ impl GameManager {
             println!("{}. {}", game.game_id, game.title);
         }
 
-        let input = self.get_user_input("Select a game (enter game ID): ");
+        let input = self
+            .get_user_input("Select a game (enter game ID): ")
+            .await?;
 
         if let Ok(game_id) = input.trim().parse::<i32>() {
             self.edit_game(game_id).await?;
@@ -336,16 +1760,31 @@ impl GameManager {
         Ok(())
     }
 
-    async fn edit_game(&self, game_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+    async fn edit_game(&self, game_id: i32) -> Result<()> {
         loop {
             clear_screen();
+            let game = self.db.get_game(game_id).await?;
             let questions = self.db.get_game_questions(game_id).await?;
 
+            println!("\n=== {} ===", game.title);
+            if let Some(description) = &game.description {
+                println!("{}", description);
+            }
+            let tags = self.db.get_game_tags(game_id).await?;
+            println!("Categories: {}", format_tags(&tags));
+            println!(
+                "Play order: {}",
+                format_play_order(game.play_order, game.draw_count)
+            );
+
             println!("\nCurrent Questions:");
             for question in &questions {
                 println!(
-                    "{}. [ID: {}] {}",
-                    question.question_order, question.question_id, question.question_text
+                    "{}. [ID: {}] {} ({})",
+                    question.question_order,
+                    question.question_id,
+                    question.question_text,
+                    question.difficulty
                 );
             }
 
@@ -354,14 +1793,18 @@ impl GameManager {
             println!("2. Add Existing question");
             println!("3. Remove question");
             println!("4. Reorder questions");
-            println!("5. Back");
+            println!("5. Edit title, description and categories");
+            println!("6. Set play order");
+            println!("7. Delete game");
+            println!("8. Back");
 
-            let choice = self.get_user_input("Enter your choice: ");
+            let choice = self.get_user_input("Enter your choice: ").await?;
 
             match choice.trim() {
                 "1" => {
-                    let question_id = self.create_question_inner().await?;
-                    self.add_question_to_game(game_id, question_id).await?;
+                    if let Some(question_id) = self.create_question_inner(true).await? {
+                        self.add_question_to_game(game_id, question_id).await?;
+                    }
                 }
                 "2" => {
                     self.add_existing_question(game_id).await?;
@@ -372,7 +1815,18 @@ impl GameManager {
                 "4" => {
                     self.reorder_questions(game_id).await?;
                 }
-                "5" => break,
+                "5" => {
+                    self.edit_game_details(game_id).await?;
+                }
+                "6" => {
+                    self.set_play_order(game_id).await?;
+                }
+                "7" => {
+                    if self.delete_game(game_id).await? {
+                        break;
+                    }
+                }
+                "8" => break,
                 _ => println!("Invalid choice, please try again."),
             }
         }
@@ -380,75 +1834,419 @@ impl GameManager {
         Ok(())
     }
 
-    async fn reorder_questions(&self, game_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+    async fn set_play_order(&self, game_id: i32) -> Result<()> {
+        println!("\nPlay order:");
+        println!("1. {} (as listed)", PlayOrder::Fixed);
+        println!(
+            "2. {} (every question, new order each play)",
+            PlayOrder::Shuffled
+        );
+        println!(
+            "3. {} (some questions, picked at random each play)",
+            PlayOrder::Draw
+        );
+
+        let (play_order, draw_count) =
+            match self.get_user_input("Choose a play order: ").await?.as_str() {
+                "1" => (PlayOrder::Fixed, None),
+                "2" => (PlayOrder::Shuffled, None),
+                "3" => {
+                    let count =
+                        parse_number(&self.get_user_input("Questions to draw per play: ").await?)?;
+                    (PlayOrder::Draw, Some(count))
+                }
+                other => {
+                    return Err(TriviaError::InvalidInput(format!(
+                        "'{}' is not a play order",
+                        other
+                    )))
+                }
+            };
+
+        self.db
+            .set_game_play_order(game_id, play_order, draw_count)
+            .await?;
+        println!("Play order updated successfully!");
+        self.get_user_input("Press Enter to continue...").await?;
+        Ok(())
+    }
+
+    async fn edit_game_details(&self, game_id: i32) -> Result<()> {
+        let game = self.db.get_game(game_id).await?;
+
+        let title = self
+            .get_user_input(&format!(
+                "New title (press Enter to keep '{}'): ",
+                game.title
+            ))
+            .await?;
+        let title = if title.is_empty() { game.title } else { title };
+
+        let description = self
+            .get_user_input("New description (press Enter to keep, '-' to remove): ")
+            .await?;
+        let description = match description.as_str() {
+            "" => game.description,
+            "-" => None,
+            _ => Some(description),
+        };
+
+        let tags = self
+            .get_user_input(&format!(
+                "New categories, comma-separated (press Enter to keep '{}', '-' to remove): ",
+                format_tags(&self.db.get_game_tags(game_id).await?)
+            ))
+            .await?;
+
+        let tags = match tags.as_str() {
+            "" => None,
+            "-" => Some(Vec::new()),
+            _ => Some(parse_tags(&tags)),
+        };
+
+        self.db
+            .update_game_with_tags(game_id, &title, description.as_deref(), tags.as_deref())
+            .await?;
+        println!("Game updated successfully!");
+        self.get_user_input("Press Enter to continue...").await?;
+        Ok(())
+    }
+
+    /// Deletes a game once the admin confirms, and reports whether it was deleted.
+    async fn delete_game(&self, game_id: i32) -> Result<bool> {
+        let game = self.db.get_game(game_id).await?;
+        let usage = self.db.get_game_usage(game_id).await?;
+
+        println!(
+            "\n'{}' has {} question(s) and {} recorded session(s).",
+            game.title, usage.question_count, usage.session_count
+        );
+        println!("In the trash it can't be played and its sessions are left off the leaderboards.");
+        let confirm = self
+            .get_user_input("Move this game to the trash? (y/n): ")
+            .await?;
+
+        let deleted = confirm.eq_ignore_ascii_case("y");
+        if deleted {
+            self.db.delete_game(game_id).await?;
+            println!("Game moved to the trash.");
+        } else {
+            println!("Delete cancelled.");
+        }
+        self.get_user_input("Press Enter to continue...").await?;
+        Ok(deleted)
+    }
+
+    async fn reorder_questions(&self, game_id: i32) -> Result<()> {
         clear_screen();
         let questions = self.db.get_game_questions(game_id).await?;
+        if questions.len() < 2 {
+            println!("This game needs at least two questions to reorder.");
+            return Ok(());
+        }
 
         println!("\nCurrent Questions:");
-        for question in &questions {
+        for (position, question) in questions.iter().enumerate() {
             println!(
                 "{}. [ID: {}] {}",
-                question.question_order, question.question_id, question.question_text
+                position + 1,
+                question.question_id,
+                question.question_text
             );
         }
 
-        println!("\nEnter new order for questions (comma-separated list of question IDs)");
+        println!("\nEnter the question numbers above in their new order, comma-separated");
         println!("Example: 3,1,4,2");
-        let new_order = self.get_user_input("New order: ");
-
-        let order_ids: Vec<i32> = new_order
-            .split(',')
-            .filter_map(|s| s.trim().parse().ok())
+        let new_order = self.get_user_input("New order: ").await?;
+
+        let order = parse_permutation(&new_order, questions.len()).ok_or_else(|| {
+            TriviaError::InvalidInput(format!(
+                "list each number from 1 to {} exactly once",
+                questions.len()
+            ))
+        })?;
+        let question_ids: Vec<i32> = order
+            .into_iter()
+            .map(|index| questions[index].question_id)
             .collect();
-
-        for (new_order, &question_id) in order_ids.iter().enumerate() {
-            self.db
-                .update_question_order(game_id, question_id, new_order as i32 + 1)
-                .await?;
-        }
+        self.db
+            .reorder_game_questions(game_id, &question_ids)
+            .await?;
 
         println!("Questions reordered successfully!");
 
         Ok(())
     }
 
-    async fn play_regular_game(&self, game_id: i32) -> Result<(), Box<dyn std::error::Error>> {
-        let mut correct_answers = 0;
+    async fn answer_key(&self, question: &GameQuestionFull) -> Result<AnswerKey> {
+        Ok(match question.kind {
+            QuestionKind::TrueFalse => AnswerKey::TrueFalse(question.correct_answer),
+            QuestionKind::MultipleChoice => {
+                AnswerKey::MultipleChoice(self.db.get_question_choices(question.question_id).await?)
+            }
+            QuestionKind::FreeText => {
+                AnswerKey::FreeText(self.db.get_accepted_answers(question.question_id).await?)
+            }
+            QuestionKind::Numeric => {
+                AnswerKey::Numeric(self.db.get_numeric_answer(question.question_id).await?)
+            }
+            QuestionKind::Ordering => {
+                let mut items = self.db.get_ordering_items(question.question_id).await?;
+                // Items come back in the correct order; never show them that way.
+                while items.len() > 1 && items.windows(2).all(|w| w[0].item_order < w[1].item_order)
+                {
+                    items.shuffle(&mut rand::thread_rng());
+                }
+                AnswerKey::Ordering {
+                    items,
+                    partial_credit: question.partial_credit,
+                }
+            }
+        })
+    }
 
-        let questions = self.db.get_game_questions(game_id).await?;
+    /// Prompts for a response in the form `key` expects. Multiple-choice,
+    /// numeric and ordering prompts repeat until the input can be read.
+    /// Returns `None` if `deadline` passes first.
+    async fn read_response(
+        &self,
+        key: &AnswerKey,
+        deadline: Option<Instant>,
+    ) -> Result<Option<String>> {
+        let response = match key {
+            AnswerKey::TrueFalse(_) => {
+                let Some(input) = self
+                    .get_answer_input("Your answer (true/false): ", deadline)
+                    .await?
+                else {
+                    return Ok(None);
+                };
+                input
+            }
+            AnswerKey::MultipleChoice(choices) => {
+                for choice in choices {
+                    println!("{}. {}", choice.label(), choice.choice_text);
+                }
+                loop {
+                    let Some(input) = self
+                        .get_answer_input("Your answer (letter): ", deadline)
+                        .await?
+                    else {
+                        return Ok(None);
+                    };
+                    if let Some(index) = parse_choice(&input, choices.len()) {
+                        break choice_label(index).to_string();
+                    }
+                    println!("Please enter one of the letters shown.");
+                }
+            }
+            AnswerKey::FreeText(_) => {
+                let Some(input) = self.get_answer_input("Your answer: ", deadline).await? else {
+                    return Ok(None);
+                };
+                input
+            }
+            AnswerKey::Numeric(answer) => {
+                let prompt = match &answer.unit {
+                    Some(unit) => format!("Your answer ({}): ", unit),
+                    None => "Your answer (number): ".to_string(),
+                };
+                loop {
+                    let Some(input) = self.get_answer_input(&prompt, deadline).await? else {
+                        return Ok(None);
+                    };
+                    if answer.parse(&input).is_some() {
+                        break input;
+                    }
+                    println!("Please enter a number, with a point for decimals (e.g. 1.5).");
+                }
+            }
+            AnswerKey::Ordering { items, .. } => {
+                for (i, item) in items.iter().enumerate() {
+                    println!("{}. {}", i + 1, item.item_text);
+                }
+                println!("Enter the numbers in the correct order, separated by commas.");
+                loop {
+                    let Some(input) = self.get_answer_input("Your order: ", deadline).await? else {
+                        return Ok(None);
+                    };
+                    if let Some(indices) = parse_permutation(&input, items.len()) {
+                        // Record correct positions rather than the shuffled
+                        // numbers, which mean nothing once the question is gone.
+                        let positions: Vec<String> = indices
+                            .iter()
+                            .map(|&i| items[i].item_order.to_string())
+                            .collect();
+                        break positions.join(",");
+                    }
+                    println!("Please use each number from 1 to {} once.", items.len());
+                }
+            }
+        };
+        Ok(Some(response))
+    }
+
+    async fn play_game(&self, game_id: i32, mode: GameMode) -> Result<()> {
+        let player_id = self
+            .current_player
+            .as_ref()
+            .map(|p| p.player_id)
+            .ok_or(TriviaError::Unauthorized)?;
+
+        let timed = mode == GameMode::Timed;
+        let speed_bonus = timed && self.settings.speed_bonus;
+        let mut correct_answers = 0;
+        let mut score = 0.0;
+        let mut max_score = 0.0;
+        let mut bonus_total = 0.0;
+
+        let game = self.db.get_game(game_id).await?;
+        let mut questions = self.db.get_game_questions(game_id).await?;
+        let seed = (game.play_order != PlayOrder::Fixed).then(rand::random::<i64>);
+        if let Some(seed) = seed {
+            game.play_order
+                .arrange(&mut questions, game.draw_count, seed as u64);
+        }
+        let session_id = self
+            .db
+            .start_session(player_id, game_id, mode, seed)
+            .await?;
+        let mut review = Vec::with_capacity(questions.len());
 
         for (i, question) in questions.iter().enumerate() {
+            let key = self.answer_key(question).await?;
+
             clear_screen();
-            println!("\nQuestion {} of {}", i + 1, questions.len());
+            let asked_at = Instant::now();
+            let points = question.difficulty.points();
+            println!(
+                "\nQuestion {} of {} ({}, {})",
+                i + 1,
+                questions.len(),
+                question.difficulty,
+                format_points(points)
+            );
+            let limit = self.time_limit(question);
+            if timed {
+                println!("Time limit: {}s", limit.as_secs());
+            }
             println!("{}", question.question_text);
-            print!("Your answer (true/false): ");
-            io::stdout().flush()?;
 
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
+            let response = self
+                .read_response(&key, timed.then(|| asked_at + limit))
+                .await?;
+            // Answers that miss the deadline are recorded as taking the full limit.
+            let elapsed = if timed {
+                asked_at.elapsed().min(limit)
+            } else {
+                asked_at.elapsed()
+            };
+            let response_time_ms = i32::try_from(elapsed.as_millis()).unwrap_or(i32::MAX);
+
+            let timed_out = response.is_none();
+            let input = response.unwrap_or_default();
+            let credit = if timed_out { 0.0 } else { key.score(&input) };
+            let correct = credit == 1.0;
 
-            let answer = input.trim().to_lowercase() == "true";
-            let correct = answer == question.correct_answer;
+            let submitted: String = input.chars().take(MAX_SUBMITTED_ANSWER_LEN).collect();
+            self.db
+                .record_answer(
+                    session_id,
+                    question.question_id,
+                    &submitted,
+                    correct,
+                    credit,
+                    response_time_ms,
+                )
+                .await?;
 
-            if correct {
-                correct_answers += 1;
-                println!("Correct!");
+            let bonus = if speed_bonus && correct {
+                speed_bonus_points(points, elapsed, limit)
             } else {
+                0.0
+            };
+            score += credit * points;
+            max_score += points;
+            bonus_total += bonus;
+            if timed_out {
+                println!("\nTime's up! Correct answer is: {}", key);
+            } else if correct {
+                correct_answers += 1;
+                if bonus > 0.0 {
+                    println!(
+                        "Correct! +{} (+{} speed bonus)",
+                        format_score(points),
+                        format_score(bonus)
+                    );
+                } else {
+                    println!("Correct! +{}", format_score(points));
+                }
+            } else if credit > 0.0 {
                 println!(
-                    "Incorrect! Correct answer is: {}",
-                    question.correct_answer.to_bool()
+                    "Partly correct ({:.0}% credit, +{}). Correct answer is: {}",
+                    credit * 100.0,
+                    format_score(credit * points),
+                    key
                 );
+            } else {
+                println!("Incorrect! Correct answer is: {}", key);
+            }
+            print_explanation(question);
+            if i + 1 < questions.len() {
+                self.get_user_input("\nPress Enter for the next question...")
+                    .await?;
+            } else {
+                self.get_user_input("\nPress Enter to see your results...")
+                    .await?;
             }
+
+            review.push(ReviewItem {
+                question,
+                key,
+                response: input,
+                credit,
+                timed_out,
+            });
         }
 
+        self.db
+            .finish_session(
+                session_id,
+                correct_answers,
+                questions.len() as i32,
+                score,
+                max_score,
+                bonus_total,
+            )
+            .await?;
+
         clear_screen();
         println!("\nGame Over!\n\n");
+        match seed {
+            Some(seed) => println!("Session #{} (question order seed {})", session_id, seed),
+            None => println!("Session #{}", session_id),
+        }
         println!(
             "You got {} out of {} questions correct!",
             correct_answers,
             questions.len()
         );
-        let percentage = (correct_answers as f64 / questions.len() as f64) * 100.0;
+        println!(
+            "You scored {} out of {} points.",
+            format_score(score),
+            format_score(max_score)
+        );
+        if bonus_total > 0.0 {
+            println!(
+                "Speed bonuses earned you {} more.",
+                format_points(bonus_total)
+            );
+        }
+        let percentage = if max_score > 0.0 {
+            (score / max_score) * 100.0
+        } else {
+            0.0
+        };
         println!("Score: {:.1}%", percentage);
 
         if percentage == 100.0 {
@@ -461,10 +2259,366 @@ impl GameManager {
             println!("Keep practicing!");
         };
 
-        println!("\nPress Enter to continue...");
-        io::stdout().flush()?;
-        let mut temp = String::new();
-        io::stdin().read_line(&mut temp)?;
+        let leaderboard = self
+            .db
+            .get_game_leaderboard(game_id, mode, LeaderboardBasis::Best, i32::MAX)
+            .await?;
+        if let Some(entry) = leaderboard.iter().find(|e| e.player_id == player_id) {
+            println!(
+                "\nYour best attempt ranks #{} of {} player(s) on this game.",
+                entry.rank,
+                leaderboard.len()
+            );
+        }
+
+        let input = self
+            .get_user_input("\nReview your answers? (y/n): ")
+            .await?;
+        if input.eq_ignore_ascii_case("y") {
+            clear_screen();
+            println!("\n=== Answer Review ===");
+            for (i, item) in review.iter().enumerate() {
+                let verdict = if item.timed_out {
+                    "out of time".to_string()
+                } else if item.credit == 1.0 {
+                    "correct".to_string()
+                } else if item.credit > 0.0 {
+                    format!("{:.0}% credit", item.credit * 100.0)
+                } else {
+                    "incorrect".to_string()
+                };
+                println!("\n{}. {}", i + 1, item.question.question_text);
+                println!(
+                    "Your answer: {} ({})",
+                    item.key.describe_response(&item.response),
+                    verdict
+                );
+                if item.credit < 1.0 {
+                    println!("Correct answer: {}", item.key);
+                }
+                print_explanation(item.question);
+            }
+        }
+
+        self.get_user_input("\nPress Enter to continue...").await?;
         Ok(())
     }
 }
+
+/// Redraws the time left on the top line of the screen, leaving the cursor
+/// where the player is typing.
+fn draw_countdown(left: Duration) -> io::Result<()> {
+    let seconds = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+    print!("\x1B7\x1B[1;1H\x1B[2KTime left: {}s\x1B8", seconds);
+    io::stdout().flush()
+}
+
+/// Extra points for a correct answer given after `elapsed` of a `limit`,
+/// rounded to hundredths.
+fn speed_bonus_points(points: f64, elapsed: Duration, limit: Duration) -> f64 {
+    let left = limit.saturating_sub(elapsed).as_secs_f64() / limit.as_secs_f64();
+    (points * SPEED_BONUS_SHARE * left * 100.0).round() / 100.0
+}
+
+fn print_explanation(question: &GameQuestionFull) {
+    if let Some(explanation) = &question.explanation {
+        println!("Explanation: {}", explanation);
+    }
+    if let Some(source) = &question.source {
+        println!("Source: {}", source);
+    }
+}
+
+fn print_kind_menu() {
+    println!("1. True/False");
+    println!("2. Multiple choice");
+    println!("3. Free text");
+    println!("4. Numeric");
+    println!("5. Ordering");
+}
+
+fn print_difficulty_menu() {
+    for (i, difficulty) in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
+        .iter()
+        .enumerate()
+    {
+        println!(
+            "{}. {} ({})",
+            i + 1,
+            difficulty,
+            format_points(difficulty.points())
+        );
+    }
+}
+
+/// Reads a choice from [`print_difficulty_menu`].
+fn parse_difficulty(input: &str) -> Result<Difficulty> {
+    match input {
+        "1" => Ok(Difficulty::Easy),
+        "2" => Ok(Difficulty::Medium),
+        "3" => Ok(Difficulty::Hard),
+        other => Err(TriviaError::InvalidInput(format!(
+            "'{}' is not a difficulty",
+            other
+        ))),
+    }
+}
+
+/// Reads a choice from [`print_kind_menu`].
+fn parse_kind(input: &str) -> Result<QuestionKind> {
+    match input {
+        "1" => Ok(QuestionKind::TrueFalse),
+        "2" => Ok(QuestionKind::MultipleChoice),
+        "3" => Ok(QuestionKind::FreeText),
+        "4" => Ok(QuestionKind::Numeric),
+        "5" => Ok(QuestionKind::Ordering),
+        other => Err(TriviaError::InvalidInput(format!(
+            "'{}' is not a question type",
+            other
+        ))),
+    }
+}
+
+fn parse_date(input: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(input, "%Y-%m-%d").map_err(|_| {
+        TriviaError::InvalidInput(format!("'{}' is not a date like 2024-01-31", input))
+    })
+}
+
+/// Lists tag names for display, or "(none)".
+fn format_tags(tags: &[String]) -> String {
+    if tags.is_empty() {
+        "(none)".to_string()
+    } else {
+        tags.join(", ")
+    }
+}
+
+/// Reads a time limit in seconds; an empty input means the default.
+fn parse_time_limit(input: &str) -> Result<Option<i32>> {
+    if input.trim().is_empty() {
+        Ok(None)
+    } else {
+        parse_number(input).map(Some)
+    }
+}
+
+fn parse_number(input: &str) -> Result<i32> {
+    input
+        .trim()
+        .parse()
+        .map_err(|_| TriviaError::InvalidInput(format!("'{}' is not a number", input.trim())))
+}
+
+/// Reads a numeric tolerance: "5" is absolute, "2%" is relative and an empty
+/// input means the answer must be exact.
+fn parse_tolerance(input: &str) -> Result<(f64, bool)> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok((0.0, false));
+    }
+    let (number, percent) = match input.strip_suffix('%') {
+        Some(number) => (number, true),
+        None => (input, false),
+    };
+    matching::parse_number(number)
+        .map(|tolerance| (tolerance, percent))
+        .ok_or_else(|| TriviaError::InvalidInput(format!("'{}' is not a tolerance", input)))
+}
+
+/// Shows whole scores without decimals and partial-credit scores to two places.
+fn format_score(score: f64) -> String {
+    if score.fract() == 0.0 {
+        format!("{}", score)
+    } else {
+        format!("{:.2}", score)
+    }
+}
+
+/// Describes a game's play order, e.g. "Random draw of 5 questions".
+fn format_play_order(play_order: PlayOrder, draw_count: Option<i32>) -> String {
+    match (play_order, draw_count) {
+        (PlayOrder::Draw, Some(1)) => format!("{} of 1 question", play_order),
+        (PlayOrder::Draw, Some(count)) => format!("{} of {} questions", play_order, count),
+        (play_order, _) => play_order.to_string(),
+    }
+}
+
+/// "1 life", "3 lives" and so on.
+fn format_lives(lives: i32) -> String {
+    let unit = if lives == 1 { "life" } else { "lives" };
+    format!("{} {}", lives, unit)
+}
+
+/// "1 day", "6 days" and so on.
+fn format_days(days: i32) -> String {
+    let unit = if days == 1 { "day" } else { "days" };
+    format!("{} {}", days, unit)
+}
+
+/// "1 point", "3 points" and so on.
+fn format_points(points: f64) -> String {
+    let unit = if points == 1.0 { "point" } else { "points" };
+    format!("{} {}", format_score(points), unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::MemoryStore;
+
+    fn manager(store: MemoryStore, script: &[&str]) -> GameManager<MemoryStore> {
+        GameManager::with_input(
+            store,
+            GameConfig::default(),
+            LineReader::from_lines(script.iter().copied()),
+        )
+    }
+
+    #[tokio::test]
+    async fn admin_can_create_a_player() {
+        let store = MemoryStore::new();
+        store.create_admin("admin", "secret").unwrap();
+
+        let mut game = manager(
+            store,
+            &["1", "admin", "secret", "5", "player", "secret", "9", "3"],
+        );
+        game.run().await.unwrap();
+
+        assert!(game.current_admin.is_some());
+        assert!(game.db.login_player("player", "secret").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn wrong_password_does_not_log_in() {
+        let store = MemoryStore::new();
+        store.create_admin("admin", "secret").unwrap();
+
+        // Input runs out at the main menu, which quits like choosing Exit.
+        let mut game = manager(store, &["1", "admin", "wrong"]);
+        game.run().await.unwrap();
+
+        assert!(game.current_admin.is_none());
+    }
+
+    #[tokio::test]
+    async fn playing_a_game_records_the_session() {
+        let store = MemoryStore::new();
+        let admin_id = store.create_admin("admin", "secret").unwrap();
+        store.create_new_player("player", "secret").await.unwrap();
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        for (order, answer) in [true, false].into_iter().enumerate() {
+            let text = format!("Statement {}", order + 1);
+            let question_id = store
+                .create_question(&text, answer, admin_id)
+                .await
+                .unwrap();
+            store
+                .add_question_to_game(game_id, question_id, order as i32 + 1)
+                .await
+                .unwrap();
+        }
+
+        let game_choice = game_id.to_string();
+        let mut game = manager(
+            store,
+            &[
+                "2",
+                "player",
+                "secret",
+                "1",
+                &game_choice,
+                "true",
+                "",
+                "true",
+                "",
+                "n",
+            ],
+        );
+        game.run().await.unwrap();
+
+        let player_id = game.current_player.as_ref().unwrap().player_id;
+        let sessions = game.db.get_player_sessions(player_id).await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        let session = &sessions[0];
+        assert!(session.ended_at.is_some());
+        assert_eq!((session.correct_answers, session.total_questions), (1, 2));
+        assert_eq!(session.score * 2.0, session.max_score);
+
+        let answers = game
+            .db
+            .get_session_answers(session.session_id)
+            .await
+            .unwrap();
+        let correct: Vec<bool> = answers.iter().map(|a| a.is_correct).collect();
+        assert_eq!(correct, [true, false]);
+    }
+
+    #[tokio::test]
+    async fn a_drawn_session_can_be_rebuilt_from_its_seed() {
+        let store = MemoryStore::new();
+        let admin_id = store.create_admin("admin", "secret").unwrap();
+        store.create_new_player("player", "secret").await.unwrap();
+        let game_id = store.create_game("Quiz", None, admin_id).await.unwrap();
+        for order in 1..=5 {
+            let text = format!("Statement {}", order);
+            let question_id = store.create_question(&text, true, admin_id).await.unwrap();
+            store
+                .add_question_to_game(game_id, question_id, order)
+                .await
+                .unwrap();
+        }
+        store
+            .set_game_play_order(game_id, PlayOrder::Draw, Some(3))
+            .await
+            .unwrap();
+
+        let game_choice = game_id.to_string();
+        let mut game = manager(
+            store,
+            &[
+                "2",
+                "player",
+                "secret",
+                "1",
+                &game_choice,
+                "true",
+                "",
+                "true",
+                "",
+                "true",
+                "",
+                "n",
+            ],
+        );
+        game.run().await.unwrap();
+
+        // The session keeps the order it was played with, whatever the game
+        // is set to later.
+        game.db
+            .set_game_play_order(game_id, PlayOrder::Fixed, None)
+            .await
+            .unwrap();
+        let player_id = game.current_player.as_ref().unwrap().player_id;
+        let session = game.db.get_player_sessions(player_id).await.unwrap()[0].clone();
+        assert!(session.seed.is_some());
+        assert_eq!(
+            (session.play_order, session.draw_count),
+            (PlayOrder::Draw, Some(3))
+        );
+
+        let mut questions = game.db.get_game_questions(game_id).await.unwrap();
+        session.arrange(&mut questions);
+        let rebuilt: Vec<i32> = questions.iter().map(|q| q.question_id).collect();
+        let played: Vec<i32> = game
+            .db
+            .get_session_answers(session.session_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|a| a.question_id)
+            .collect();
+        assert_eq!(rebuilt, played);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::error::{Result, TriviaError};
+use tokio::io::{self, AsyncBufReadExt, BufReader};
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{self, Instant};
+
+/// Line input for the CLI that never blocks the runtime. A background task
+/// reads stdin and hands each line over a channel, so a prompt can wait with
+/// a timeout or be cancelled by dropping its future, e.g. in `tokio::select!`,
+/// without losing the next line typed.
+pub struct LineReader {
+    lines: Mutex<mpsc::UnboundedReceiver<String>>,
+}
+
+impl LineReader {
+    /// Starts reading stdin. Must be called from inside the tokio runtime.
+    ///
+    /// Tokio reads stdin on a blocking thread that cannot be interrupted, so
+    /// the runtime has to be shut down without waiting for it.
+    pub fn new() -> Self {
+        let (sender, lines) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut stdin = BufReader::new(io::stdin()).lines();
+            while let Ok(Some(line)) = stdin.next_line().await {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            lines: Mutex::new(lines),
+        }
+    }
+
+    /// Reads the given lines instead of stdin, then reports the input closed.
+    /// Lets a whole session be scripted, e.g. in tests.
+    pub fn from_lines<I>(lines: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let (sender, lines_rx) = mpsc::unbounded_channel();
+        for line in lines {
+            // The receiver is still held here, so sending can't fail.
+            let _ = sender.send(line.into());
+        }
+        Self {
+            lines: Mutex::new(lines_rx),
+        }
+    }
+
+    /// Waits for the next line, failing with [`TriviaError::InputClosed`]
+    /// once stdin has been closed and every line read.
+    pub async fn read_line(&self) -> Result<String> {
+        self.lines
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or(TriviaError::InputClosed)
+    }
+
+    /// Like [`Self::read_line`], but gives up at `deadline`, returning
+    /// `Ok(None)` if no line arrived in time.
+    pub async fn read_line_until(&self, deadline: Instant) -> Result<Option<String>> {
+        match time::timeout_at(deadline, self.read_line()).await {
+            Ok(line) => line.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl Default for LineReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
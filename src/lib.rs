@@ -1,7 +1,12 @@
 pub mod config;
 pub mod db;
+pub mod duplicates;
+pub mod error;
 pub mod game;
+pub mod input;
+pub mod matching;
 pub mod models;
+pub mod password;
 
 pub fn clear_screen() {
     // Clear terminal screen
@@ -1,19 +1,146 @@
-use csci211_project_two::{config, db, game};
-use sqlx::mysql::MySqlPoolOptions;
+use csci211_project_two::{config, db::TriviaStore, error::Result, game, input::LineReader};
+use std::io::{self, Write};
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+const USAGE: &str = "Usage: projecttwo [migrate status|up|down]";
+
+fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let result = runtime.block_on(start());
+    // The game's stdin reader may still be blocked on a read that can't be
+    // cancelled; waiting for it would hang until Enter is pressed.
+    runtime.shutdown_background();
+    result
+}
+
+async fn start() -> std::result::Result<(), Box<dyn std::error::Error>> {
     let config = config::Config::load()?;
+    let url = config.database.url.as_str();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // The URL scheme picks the backend; each one is behind its own cargo feature.
+    match url.split(':').next().unwrap_or_default() {
+        #[cfg(feature = "mysql")]
+        "mysql" | "mariadb" => {
+            use sqlx::mysql::MySqlPoolOptions;
 
-    let pool = MySqlPoolOptions::new()
-        .max_connections(config.database.max_connections)
-        .connect(&config.database.url)
-        .await?;
+            let pool = MySqlPoolOptions::new()
+                .max_connections(config.database.max_connections)
+                .connect(url)
+                .await?;
 
-    let db = db::Database::new(pool);
-    let mut game_manager = game::GameManager::new(db);
+            let db = csci211_project_two::db::MySqlStore::new(pool);
+            run(db, &args, &config).await?;
+        }
+        #[cfg(feature = "sqlite")]
+        "sqlite" => {
+            use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+            use std::str::FromStr;
 
-    game_manager.run().await?;
+            let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+            let pool = SqlitePoolOptions::new()
+                .max_connections(config.database.max_connections)
+                .connect_with(options)
+                .await?;
+
+            let db = csci211_project_two::db::SqliteStore::new(pool);
+            run(db, &args, &config).await?;
+        }
+        "memory" => {
+            let db = csci211_project_two::db::MemoryStore::from_url(url)?;
+            run(db, &args, &config).await?;
+        }
+        scheme => {
+            return Err(format!(
+                "Unsupported database URL scheme '{}' (is the matching cargo feature enabled?)",
+                scheme
+            )
+            .into())
+        }
+    }
 
     Ok(())
 }
+
+async fn run<S: TriviaStore>(db: S, args: &[String], config: &config::Config) -> Result<()> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        [] => {
+            // One reader for the whole session, so no typed line is lost
+            // between the upgrade prompt and the game.
+            let input = LineReader::new();
+            if !upgrade_schema(&db, config.database.auto_migrate, &input).await? {
+                return Ok(());
+            }
+            let mut game_manager = game::GameManager::with_input(db, config.game.clone(), input);
+            game_manager.run().await
+        }
+        ["migrate", "status"] => {
+            for migration in db.migration_status().await? {
+                println!(
+                    "{:>4}  {:<8}  {}",
+                    migration.version,
+                    if migration.applied {
+                        "applied"
+                    } else {
+                        "pending"
+                    },
+                    migration.description
+                );
+            }
+            Ok(())
+        }
+        ["migrate", "up"] => {
+            db.migrate_up().await?;
+            println!("Database schema is up to date.");
+            Ok(())
+        }
+        ["migrate", "down"] => {
+            match db.migrate_down().await? {
+                Some(version) => println!("Reverted migration {}.", version),
+                None => println!("No migrations to revert."),
+            }
+            Ok(())
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            Ok(())
+        }
+    }
+}
+
+/// Applies pending migrations before the game starts, asking first unless
+/// `auto_migrate` is set. Returns false if the upgrade was declined.
+async fn upgrade_schema<S: TriviaStore>(
+    db: &S,
+    auto_migrate: bool,
+    input: &LineReader,
+) -> Result<bool> {
+    let pending = db
+        .migration_status()
+        .await?
+        .iter()
+        .filter(|m| !m.applied)
+        .count();
+
+    if pending == 0 {
+        return Ok(true);
+    }
+
+    if !auto_migrate {
+        print!(
+            "The database schema has {} pending migration(s). Apply them now? (y/n): ",
+            pending
+        );
+        io::stdout().flush()?;
+
+        if !input.read_line().await?.trim().eq_ignore_ascii_case("y") {
+            println!("Run `projecttwo migrate up` when you are ready to upgrade.");
+            return Ok(false);
+        }
+    }
+
+    db.migrate_up().await?;
+    println!("Applied {} migration(s).", pending);
+    Ok(true)
+}
@@ -0,0 +1,204 @@
+//! Loose text comparison for typed answers.
+
+/// Lowercases `text` and drops everything that isn't a letter or digit, so
+/// "New-York!" and "new york" compare equal.
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Lowercase words of `text`, split at anything that isn't a letter or digit.
+pub fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Edit distance between `a` and `b` in characters, where an insertion,
+/// deletion, substitution or swap of two adjacent characters each count as one
+/// edit (optimal string alignment).
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j - 1] + cost)
+                .min(previous[j] + 1)
+                .min(current[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before_previous[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Characters of the expected answer needed for each typo forgiven, so answers
+/// shorter than this must match exactly.
+const CHARS_PER_TYPO: usize = 4;
+
+/// True if `response` matches `expected` after normalization, allowing up to
+/// `max_distance` single-character edits but no more than one for every
+/// [`CHARS_PER_TYPO`] characters of `expected`. An empty response never
+/// matches.
+pub fn is_match(response: &str, expected: &str, max_distance: usize) -> bool {
+    let response = normalize(response);
+    if response.is_empty() {
+        return false;
+    }
+    let expected = normalize(expected);
+    let max_distance = max_distance.min(expected.chars().count() / CHARS_PER_TYPO);
+    edit_distance(&response, &expected) <= max_distance
+}
+
+/// Parses a typed number, accepting thousands separators ("1,234,567" or
+/// "1 234 567"), decimals, a leading sign and the Unicode minus sign. A
+/// separator anywhere but between groups of three digits, as in a decimal
+/// comma ("1,5"), makes the input unreadable rather than a different number.
+pub fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim().replace('\u{2212}', "-");
+    let (sign, rest) = text.split_at(usize::from(text.starts_with(['-', '+'])));
+    let (integer, tail) = rest.split_at(rest.find(['.', 'e', 'E']).unwrap_or(rest.len()));
+    let cleaned = format!("{}{}{}", sign, ungroup(integer)?, tail);
+
+    // `f64::from_str` also takes "inf" and "NaN", which aren't answers.
+    if !cleaned
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'))
+    {
+        return None;
+    }
+    cleaned.parse().ok().filter(|n: &f64| n.is_finite())
+}
+
+/// Removes the separators from the whole-number part of a typed number.
+/// Returns `None` unless one kind of separator splits it into groups of
+/// three digits after a first group of one to three.
+fn ungroup(integer: &str) -> Option<String> {
+    let is_separator = |c: char| matches!(c, ',' | '_' | '\'') || c.is_whitespace();
+    let Some(separator) = integer.chars().find(|&c| is_separator(c)) else {
+        return Some(integer.to_string());
+    };
+
+    let groups: Vec<&str> = integer.split(separator).collect();
+    let well_formed = groups.iter().enumerate().all(|(i, group)| {
+        let sized = if i == 0 {
+            (1..=3).contains(&group.len())
+        } else {
+            group.len() == 3
+        };
+        sized && group.chars().all(|c| c.is_ascii_digit())
+    });
+    well_formed.then(|| groups.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_number_reads_plain_numbers() {
+        assert_eq!(parse_number("42"), Some(42.0));
+        assert_eq!(parse_number(" -3.5 "), Some(-3.5));
+        assert_eq!(parse_number("+.5"), Some(0.5));
+        assert_eq!(parse_number("\u{2212}7"), Some(-7.0));
+        assert_eq!(parse_number("1.5e3"), Some(1500.0));
+    }
+
+    #[test]
+    fn parse_number_strips_separators_between_groups_of_three() {
+        assert_eq!(parse_number("1,234"), Some(1234.0));
+        assert_eq!(parse_number("1,234,567.25"), Some(1234567.25));
+        assert_eq!(parse_number("-12 345"), Some(-12345.0));
+        assert_eq!(parse_number("1_000_000"), Some(1_000_000.0));
+        assert_eq!(parse_number("1'000"), Some(1000.0));
+        assert_eq!(parse_number("123,456"), Some(123456.0));
+    }
+
+    #[test]
+    fn parse_number_rejects_misplaced_separators() {
+        // A decimal comma must not be read as a thousands separator.
+        assert_eq!(parse_number("1,5"), None);
+        assert_eq!(parse_number("1 5"), None);
+        assert_eq!(parse_number("12,34"), None);
+        assert_eq!(parse_number("1234,567"), None);
+        assert_eq!(parse_number(",123"), None);
+        assert_eq!(parse_number("1,,234"), None);
+        assert_eq!(parse_number("1,234,"), None);
+        assert_eq!(parse_number("1,234 567"), None);
+        assert_eq!(parse_number("1.234,5"), None);
+        assert_eq!(parse_number("3.141 592"), None);
+    }
+
+    #[test]
+    fn parse_number_rejects_words() {
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("ten"), None);
+        assert_eq!(parse_number("inf"), None);
+        assert_eq!(parse_number("NaN"), None);
+        assert_eq!(parse_number("1e999"), None);
+    }
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "kitten"), 0);
+        assert_eq!(edit_distance("kitten", "sitten"), 1);
+        assert_eq!(edit_distance("kitten", "kiten"), 1);
+        assert_eq!(edit_distance("kitten", "kitttten"), 2);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn edit_distance_counts_an_adjacent_swap_as_one_edit() {
+        assert_eq!(edit_distance("einstein", "eisntein"), 1);
+        assert_eq!(edit_distance("ab", "ba"), 1);
+    }
+
+    #[test]
+    fn is_match_ignores_case_spacing_and_punctuation() {
+        assert!(is_match("new york", "New-York!", 0));
+        assert!(is_match("  EINSTEIN ", "Einstein", 0));
+        assert!(!is_match("", "Einstein", 2));
+        assert!(!is_match("!?", "Einstein", 2));
+    }
+
+    #[test]
+    fn is_match_forgives_typos_up_to_max_distance() {
+        assert!(is_match("Einstien", "Einstein", 1));
+        assert!(!is_match("Einstien", "Einstein", 0));
+        assert!(!is_match("Eisntien", "Einstein", 1));
+        assert!(is_match("Eisntien", "Einstein", 2));
+    }
+
+    #[test]
+    fn is_match_does_not_fuzz_short_answers() {
+        assert!(!is_match("a", "b", 1));
+        assert!(!is_match("b", "ab", 1));
+        assert!(!is_match("cut", "cat", 1));
+        assert!(is_match("cat", "cat", 1));
+        assert!(is_match("bard", "bird", 1));
+    }
+
+    #[test]
+    fn is_match_allows_one_typo_per_four_characters() {
+        // Seven characters only earn one typo, whatever the question allows.
+        assert!(is_match("Neptuen", "Neptune", 3));
+        assert!(!is_match("Nepteun", "Neptune", 3));
+        assert!(is_match("Missisipi", "Mississippi", 3));
+        assert!(!is_match("Misisipi", "Mississippi", 3));
+    }
+}
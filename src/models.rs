@@ -1,8 +1,14 @@
-use chrono::Utc;
+use crate::error::TriviaError;
+use crate::matching;
+use chrono::{NaiveDate, Utc};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::fmt;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
 pub struct Admin {
     pub admin_id: i32,
     pub username: String,
@@ -18,29 +24,753 @@ pub struct Player {
     pub created_at: Option<chrono::DateTime<Utc>>,
 }
 
-#[derive(Debug, FromRow, Serialize, Deserialize)]
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct Game {
     pub game_id: i32,
     pub title: String,
     pub description: Option<String>,
+    pub play_order: PlayOrder,
+    /// How many questions a [`PlayOrder::Draw`] game plays.
+    pub draw_count: Option<i32>,
+}
+
+/// How a game's questions are put in order each time it is played, stored in
+/// `games.play_order`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlayOrder {
+    /// The order set by the admin.
+    #[default]
+    Fixed,
+    /// Every question, in a new order each play.
+    Shuffled,
+    /// `draw_count` questions picked at random from the game's questions.
+    Draw,
+}
+
+impl PlayOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlayOrder::Fixed => "fixed",
+            PlayOrder::Shuffled => "shuffled",
+            PlayOrder::Draw => "draw",
+        }
+    }
+
+    /// Puts a game's questions, given in their fixed order, in the order they
+    /// are played. The same `seed` always gives the same result, which is how
+    /// a session's order is reproduced from `game_sessions.seed`.
+    pub fn arrange<T>(&self, questions: &mut Vec<T>, draw_count: Option<i32>, seed: u64) {
+        if *self == PlayOrder::Fixed {
+            return;
+        }
+        questions.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+        if *self == PlayOrder::Draw {
+            questions.truncate(draw_count.unwrap_or(0).max(0) as usize);
+        }
+    }
+}
+
+impl fmt::Display for PlayOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayOrder::Fixed => write!(f, "Fixed order"),
+            PlayOrder::Shuffled => write!(f, "Shuffled"),
+            PlayOrder::Draw => write!(f, "Random draw"),
+        }
+    }
 }
 
-#[derive(Debug, FromRow, Serialize, Deserialize)]
+impl TryFrom<&str> for PlayOrder {
+    type Error = TriviaError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "fixed" => Ok(PlayOrder::Fixed),
+            "shuffled" => Ok(PlayOrder::Shuffled),
+            "draw" => Ok(PlayOrder::Draw),
+            _ => Err(TriviaError::InvalidInput(format!(
+                "'{}' is not a valid play order",
+                value
+            ))),
+        }
+    }
+}
+
+impl<DB: sqlx::Database> sqlx::Type<DB> for PlayOrder
+where
+    String: sqlx::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as sqlx::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as sqlx::Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for PlayOrder
+where
+    String: sqlx::Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        Ok(PlayOrder::try_from(
+            <String as sqlx::Decode<DB>>::decode(value)?.as_str(),
+        )?)
+    }
+}
+
+/// Longest tag name `tags.name` can hold.
+pub const MAX_TAG_LEN: usize = 50;
+
+/// A tag with how many live questions and games carry it. Tags on games are
+/// the categories players browse by.
+#[derive(Debug, FromRow, Clone)]
+pub struct Tag {
+    pub tag_id: i32,
+    pub name: String,
+    pub question_count: i64,
+    pub game_count: i64,
+}
+
+/// Canonical form of a tag name: trimmed, lowercased and with inner runs of
+/// whitespace collapsed to one space.
+pub fn normalize_tag(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Splits a comma-separated list of tags, normalizing each and dropping
+/// blanks and repeats.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').map(normalize_tag) {
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// What kind of record a [`TrashItem`] is.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrashKind {
+    Player,
+    Game,
+    Question,
+}
+
+impl TrashKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrashKind::Player => "player",
+            TrashKind::Game => "game",
+            TrashKind::Question => "question",
+        }
+    }
+}
+
+impl fmt::Display for TrashKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrashKind::Player => write!(f, "Player"),
+            TrashKind::Game => write!(f, "Game"),
+            TrashKind::Question => write!(f, "Question"),
+        }
+    }
+}
+
+impl TryFrom<&str> for TrashKind {
+    type Error = TriviaError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "player" => Ok(TrashKind::Player),
+            "game" => Ok(TrashKind::Game),
+            "question" => Ok(TrashKind::Question),
+            _ => Err(TriviaError::InvalidInput(format!(
+                "'{}' is not a kind of trash",
+                value
+            ))),
+        }
+    }
+}
+
+impl<DB: sqlx::Database> sqlx::Type<DB> for TrashKind
+where
+    String: sqlx::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as sqlx::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as sqlx::Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for TrashKind
+where
+    String: sqlx::Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        Ok(TrashKind::try_from(
+            <String as sqlx::Decode<DB>>::decode(value)?.as_str(),
+        )?)
+    }
+}
+
+/// A soft-deleted player, game or question. `name` is the username, title or
+/// question text.
+#[derive(Debug, FromRow, Clone)]
+pub struct TrashItem {
+    pub kind: TrashKind,
+    pub id: i32,
+    pub name: String,
+    pub deleted_at: Option<chrono::DateTime<Utc>>,
+}
+
+/// What depends on a game, shown before it is deleted.
+#[derive(Debug, FromRow, Clone, Copy)]
+pub struct GameUsage {
+    pub question_count: i64,
+    pub session_count: i64,
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct Question {
     pub question_id: i32,
     pub question_text: String,
+    /// Only meaningful for [`QuestionKind::TrueFalse`]; other kinds keep their
+    /// answer in their own table.
     pub correct_answer: Answer,
+    pub kind: QuestionKind,
+    /// Whether a partly right response earns a share of the point. Only
+    /// ordering questions can be partly right.
+    pub partial_credit: bool,
+    pub difficulty: Difficulty,
+    /// Seconds allowed in timed mode; `None` uses the configured default.
+    pub time_limit_secs: Option<i32>,
+    /// Shown to the player once they have answered.
+    pub explanation: Option<String>,
+    /// Where the answer can be checked, e.g. a book or URL.
+    pub source: Option<String>,
 }
 
-#[derive(Debug, FromRow, Serialize, Deserialize)]
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct GameQuestionFull {
     pub question_id: i32,
     pub question_text: String,
     pub correct_answer: Answer,
+    pub kind: QuestionKind,
+    pub partial_credit: bool,
+    pub difficulty: Difficulty,
+    pub time_limit_secs: Option<i32>,
+    pub explanation: Option<String>,
+    pub source: Option<String>,
     pub question_order: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// How a question is answered, stored in `questions.kind`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionKind {
+    TrueFalse,
+    /// Answered by letter from the rows in `question_choices`.
+    MultipleChoice,
+    /// Answered by typing; checked against the rows in `accepted_answers`.
+    FreeText,
+    /// Answered with a number; checked against its `numeric_answers` row.
+    Numeric,
+    /// Answered by putting the rows of `ordering_items` in order.
+    Ordering,
+}
+
+impl QuestionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestionKind::TrueFalse => "true_false",
+            QuestionKind::MultipleChoice => "multiple_choice",
+            QuestionKind::FreeText => "free_text",
+            QuestionKind::Numeric => "numeric",
+            QuestionKind::Ordering => "ordering",
+        }
+    }
+}
+
+impl fmt::Display for QuestionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuestionKind::TrueFalse => write!(f, "True/False"),
+            QuestionKind::MultipleChoice => write!(f, "Multiple choice"),
+            QuestionKind::FreeText => write!(f, "Free text"),
+            QuestionKind::Numeric => write!(f, "Numeric"),
+            QuestionKind::Ordering => write!(f, "Ordering"),
+        }
+    }
+}
+
+impl TryFrom<&str> for QuestionKind {
+    type Error = TriviaError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "true_false" => Ok(QuestionKind::TrueFalse),
+            "multiple_choice" => Ok(QuestionKind::MultipleChoice),
+            "free_text" => Ok(QuestionKind::FreeText),
+            "numeric" => Ok(QuestionKind::Numeric),
+            "ordering" => Ok(QuestionKind::Ordering),
+            _ => Err(TriviaError::InvalidInput(format!(
+                "'{}' is not a valid question kind",
+                value
+            ))),
+        }
+    }
+}
+
+impl<DB: sqlx::Database> sqlx::Type<DB> for QuestionKind
+where
+    String: sqlx::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as sqlx::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as sqlx::Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for QuestionKind
+where
+    String: sqlx::Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        Ok(QuestionKind::try_from(
+            <String as sqlx::Decode<DB>>::decode(value)?.as_str(),
+        )?)
+    }
+}
+
+/// How hard a question is, stored in `questions.difficulty`. Harder
+/// questions are worth more points.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    /// Points a fully correct answer earns.
+    pub fn points(&self) -> f64 {
+        match self {
+            Difficulty::Easy => 1.0,
+            Difficulty::Medium => 2.0,
+            Difficulty::Hard => 3.0,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "Easy"),
+            Difficulty::Medium => write!(f, "Medium"),
+            Difficulty::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Difficulty {
+    type Error = TriviaError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(TriviaError::InvalidInput(format!(
+                "'{}' is not a valid difficulty",
+                value
+            ))),
+        }
+    }
+}
+
+impl<DB: sqlx::Database> sqlx::Type<DB> for Difficulty
+where
+    String: sqlx::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as sqlx::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as sqlx::Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for Difficulty
+where
+    String: sqlx::Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        Ok(Difficulty::try_from(
+            <String as sqlx::Decode<DB>>::decode(value)?.as_str(),
+        )?)
+    }
+}
+
+/// Shortest time limit a question may have in timed mode, in seconds.
+pub const MIN_TIME_LIMIT_SECS: i32 = 5;
+/// Longest time limit a question may have in timed mode, in seconds.
+pub const MAX_TIME_LIMIT_SECS: i32 = 600;
+
+/// How a session was played, stored in `game_sessions.mode`. Each mode has
+/// its own leaderboards.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameMode {
+    #[default]
+    Regular,
+    /// Every question has a time limit and fast answers may earn a bonus.
+    Timed,
+}
+
+impl GameMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameMode::Regular => "regular",
+            GameMode::Timed => "timed",
+        }
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameMode::Regular => write!(f, "Regular"),
+            GameMode::Timed => write!(f, "Timed"),
+        }
+    }
+}
+
+impl TryFrom<&str> for GameMode {
+    type Error = TriviaError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "regular" => Ok(GameMode::Regular),
+            "timed" => Ok(GameMode::Timed),
+            _ => Err(TriviaError::InvalidInput(format!(
+                "'{}' is not a valid game mode",
+                value
+            ))),
+        }
+    }
+}
+
+impl<DB: sqlx::Database> sqlx::Type<DB> for GameMode
+where
+    String: sqlx::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as sqlx::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as sqlx::Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for GameMode
+where
+    String: sqlx::Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        Ok(GameMode::try_from(
+            <String as sqlx::Decode<DB>>::decode(value)?.as_str(),
+        )?)
+    }
+}
+
+/// Fewest choices a multiple-choice question may have.
+pub const MIN_CHOICES: usize = 2;
+/// Most choices a multiple-choice question may have (lettered A-F).
+pub const MAX_CHOICES: usize = 6;
+
+/// One option of a multiple-choice question. `choice_order` starts at 1 and
+/// determines the letter shown to the player.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct QuestionChoice {
+    pub choice_id: i32,
+    pub question_id: i32,
+    pub choice_order: i32,
+    pub choice_text: String,
+    pub is_correct: bool,
+}
+
+impl QuestionChoice {
+    pub fn label(&self) -> char {
+        choice_label(self.choice_order as usize - 1)
+    }
+}
+
+/// Letter for the zero-based choice `index`: 0 is 'A', 1 is 'B', ...
+pub fn choice_label(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+/// Maps a choice letter (case-insensitive) to its zero-based index, if it is
+/// one of the first `count` letters.
+pub fn parse_choice(input: &str, count: usize) -> Option<usize> {
+    let mut chars = input.trim().chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) => {
+            (0..count).find(|&i| choice_label(i) == letter.to_ascii_uppercase())
+        }
+        _ => None,
+    }
+}
+
+/// One accepted response to a free-text question. `max_distance` is how many
+/// typos (single-character edits) are forgiven after normalization.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct AcceptedAnswer {
+    pub accepted_answer_id: i32,
+    pub question_id: i32,
+    pub answer_text: String,
+    pub max_distance: i32,
+}
+
+impl PartialEq<AcceptedAnswer> for str {
+    fn eq(&self, other: &AcceptedAnswer) -> bool {
+        matching::is_match(
+            self,
+            &other.answer_text,
+            usize::try_from(other.max_distance).unwrap_or(0),
+        )
+    }
+}
+
+impl PartialEq<AcceptedAnswer> for String {
+    fn eq(&self, other: &AcceptedAnswer) -> bool {
+        self.as_str() == other
+    }
+}
+
+/// The target of a numeric question. `tolerance` is an absolute distance from
+/// `target_value`, or a percentage of it when `tolerance_percent` is set.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct NumericAnswer {
+    pub question_id: i32,
+    pub target_value: f64,
+    pub tolerance: f64,
+    pub tolerance_percent: bool,
+    /// Shown after the value and allowed after the player's number, e.g. "km".
+    pub unit: Option<String>,
+}
+
+impl NumericAnswer {
+    /// Reads a player's response, ignoring the unit if they typed it.
+    pub fn parse(&self, response: &str) -> Option<f64> {
+        let response = response.trim();
+        let number = match self.unit.as_deref() {
+            Some(unit) if response.len() > unit.len() => {
+                let split = response.len() - unit.len();
+                match response.get(split..) {
+                    Some(suffix) if suffix.eq_ignore_ascii_case(unit) => &response[..split],
+                    _ => response,
+                }
+            }
+            _ => response,
+        };
+        matching::parse_number(number)
+    }
+
+    pub fn accepts(&self, value: f64) -> bool {
+        let allowed = if self.tolerance_percent {
+            self.target_value.abs() * self.tolerance / 100.0
+        } else {
+            self.tolerance
+        };
+        // Leave room for rounding so that e.g. 0.3 accepts 0.1 + 0.2.
+        (value - self.target_value).abs() <= allowed + 1e-9 * self.target_value.abs().max(1.0)
+    }
+}
+
+impl fmt::Display for NumericAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.target_value)?;
+        if let Some(unit) = &self.unit {
+            write!(f, " {}", unit)?;
+        }
+        if self.tolerance > 0.0 {
+            let percent = if self.tolerance_percent { "%" } else { "" };
+            write!(f, " (±{}{})", self.tolerance, percent)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fewest items an ordering question may have.
+pub const MIN_ORDERING_ITEMS: usize = 3;
+/// Most items an ordering question may have.
+pub const MAX_ORDERING_ITEMS: usize = 8;
+
+/// One item of an ordering question. `item_order` is its correct position,
+/// starting at 1.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct OrderingItem {
+    pub item_id: i32,
+    pub question_id: i32,
+    pub item_order: i32,
+    pub item_text: String,
+}
+
+/// Reads a comma-separated list of the numbers 1 to `count`, each exactly
+/// once, and returns them as zero-based indices.
+pub fn parse_permutation(input: &str, count: usize) -> Option<Vec<usize>> {
+    let mut seen = vec![false; count];
+    let mut indices = Vec::with_capacity(count);
+    for part in input.split(',') {
+        let index = part.trim().parse::<usize>().ok()?.checked_sub(1)?;
+        if index >= count || std::mem::replace(&mut seen[index], true) {
+            return None;
+        }
+        indices.push(index);
+    }
+    (indices.len() == count).then_some(indices)
+}
+
+/// The stored answer of a question, in whatever shape its kind needs.
+#[derive(Debug, Clone)]
+pub enum AnswerKey {
+    TrueFalse(Answer),
+    MultipleChoice(Vec<QuestionChoice>),
+    FreeText(Vec<AcceptedAnswer>),
+    Numeric(NumericAnswer),
+    /// `items` are in the order they were shown to the player.
+    Ordering {
+        items: Vec<OrderingItem>,
+        partial_credit: bool,
+    },
+}
+
+impl AnswerKey {
+    /// Grades a player's response, from 0.0 (wrong) to 1.0 (fully correct).
+    ///
+    /// An ordering response lists the items' correct positions in the order
+    /// the player put them, so "1,2,3" is always right whatever order the items
+    /// were shown in. Partial credit is the share of item pairs in the right
+    /// relative order, so one misplaced item costs less than a reversed list.
+    pub fn score(&self, response: &str) -> f64 {
+        match self {
+            AnswerKey::Ordering {
+                items,
+                partial_credit,
+            } => {
+                let Some(positions) = parse_permutation(response, items.len()) else {
+                    return 0.0;
+                };
+                let pairs = positions.len() * (positions.len() - 1) / 2;
+                let in_order = (0..positions.len())
+                    .flat_map(|i| (i + 1..positions.len()).map(move |j| (i, j)))
+                    .filter(|&(i, j)| positions[i] < positions[j])
+                    .count();
+
+                if in_order == pairs {
+                    1.0
+                } else if *partial_credit && pairs > 0 {
+                    in_order as f64 / pairs as f64
+                } else {
+                    0.0
+                }
+            }
+            _ => {
+                if self.is_correct(response) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// Spells out a graded response for the end-of-game review: the chosen
+    /// choice's text, or an ordering as item texts.
+    pub fn describe_response(&self, response: &str) -> String {
+        match self {
+            AnswerKey::MultipleChoice(choices) => parse_choice(response, choices.len())
+                .map(|i| format!("{}. {}", choices[i].label(), choices[i].choice_text))
+                .unwrap_or_else(|| response.to_string()),
+            AnswerKey::Ordering { items, .. } => match parse_permutation(response, items.len()) {
+                Some(positions) => {
+                    let texts: Vec<&str> = positions
+                        .iter()
+                        .filter_map(|&p| items.iter().find(|i| i.item_order as usize == p + 1))
+                        .map(|i| i.item_text.as_str())
+                        .collect();
+                    texts.join(" → ")
+                }
+                None => response.to_string(),
+            },
+            _ => response.to_string(),
+        }
+    }
+
+    /// True only for a fully correct response.
+    pub fn is_correct(&self, response: &str) -> bool {
+        match self {
+            AnswerKey::TrueFalse(answer) => (response.trim().to_lowercase() == "true") == *answer,
+            AnswerKey::MultipleChoice(choices) => {
+                parse_choice(response, choices.len()).is_some_and(|i| choices[i].is_correct)
+            }
+            AnswerKey::FreeText(accepted) => accepted.iter().any(|a| response == a),
+            AnswerKey::Numeric(answer) => answer.parse(response).is_some_and(|v| answer.accepts(v)),
+            AnswerKey::Ordering { .. } => self.score(response) == 1.0,
+        }
+    }
+}
+
+impl fmt::Display for AnswerKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswerKey::TrueFalse(answer) => write!(f, "{}", answer.to_bool()),
+            AnswerKey::MultipleChoice(choices) => match choices.iter().find(|c| c.is_correct) {
+                Some(choice) => write!(f, "{}. {}", choice.label(), choice.choice_text),
+                None => write!(f, "(no correct choice)"),
+            },
+            AnswerKey::FreeText(accepted) => {
+                let texts: Vec<&str> = accepted.iter().map(|a| a.answer_text.as_str()).collect();
+                write!(f, "{}", texts.join(" / "))
+            }
+            AnswerKey::Numeric(answer) => write!(f, "{}", answer),
+            AnswerKey::Ordering { items, .. } => {
+                let mut shown: Vec<(usize, &OrderingItem)> = items.iter().enumerate().collect();
+                shown.sort_by_key(|(_, item)| item.item_order);
+                let numbers: Vec<String> = shown.iter().map(|(i, _)| (i + 1).to_string()).collect();
+                let texts: Vec<&str> = shown
+                    .iter()
+                    .map(|(_, item)| item.item_text.as_str())
+                    .collect();
+                write!(f, "{} ({})", numbers.join(","), texts.join(" → "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 pub enum Answer {
     True,
     False,
@@ -64,16 +794,45 @@ impl PartialEq<Answer> for bool {
     }
 }
 
-impl From<i8> for Answer {
-    fn from(value: i8) -> Self {
+impl TryFrom<i8> for Answer {
+    type Error = TriviaError;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
         match value {
-            0 => Answer::False,
-            1 => Answer::True,
-            _ => panic!("Invalid value for Answer"),
+            0 => Ok(Answer::False),
+            1 => Ok(Answer::True),
+            _ => Err(TriviaError::InvalidInput(format!(
+                "{} is not a valid answer value",
+                value
+            ))),
         }
     }
 }
 
+// Answers are stored as BOOLEAN (TINYINT) columns; decode through `i8` so an
+// out-of-range value surfaces as a decode error instead of a panic.
+impl<DB: sqlx::Database> sqlx::Type<DB> for Answer
+where
+    i8: sqlx::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <i8 as sqlx::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <i8 as sqlx::Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for Answer
+where
+    i8: sqlx::Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        Ok(Answer::try_from(<i8 as sqlx::Decode<DB>>::decode(value)?)?)
+    }
+}
+
 impl From<Answer> for i8 {
     fn from(value: Answer) -> Self {
         match value {
@@ -102,9 +861,536 @@ impl From<Answer> for bool {
     }
 }
 
+/// A question's answer in the form admins author and edit it. Revisions store
+/// it as JSON so an old answer can be restored whatever its kind.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum AnswerSpec {
+    TrueFalse(bool),
+    MultipleChoice {
+        choices: Vec<String>,
+        correct_choice: usize,
+    },
+    /// Every accepted answer forgives the same number of typos.
+    FreeText {
+        accepted: Vec<String>,
+        max_distance: i32,
+    },
+    Numeric {
+        target_value: f64,
+        tolerance: f64,
+        tolerance_percent: bool,
+        unit: Option<String>,
+    },
+    /// `items` are listed in their correct order.
+    Ordering {
+        items: Vec<String>,
+        partial_credit: bool,
+    },
+}
+
+impl AnswerSpec {
+    pub fn kind(&self) -> QuestionKind {
+        match self {
+            AnswerSpec::TrueFalse(_) => QuestionKind::TrueFalse,
+            AnswerSpec::MultipleChoice { .. } => QuestionKind::MultipleChoice,
+            AnswerSpec::FreeText { .. } => QuestionKind::FreeText,
+            AnswerSpec::Numeric { .. } => QuestionKind::Numeric,
+            AnswerSpec::Ordering { .. } => QuestionKind::Ordering,
+        }
+    }
+
+    /// Value of `questions.correct_answer`, which only true/false questions use.
+    pub fn correct_answer(&self) -> bool {
+        matches!(self, AnswerSpec::TrueFalse(true))
+    }
+
+    /// Value of `questions.partial_credit`.
+    pub fn partial_credit(&self) -> bool {
+        matches!(
+            self,
+            AnswerSpec::Ordering {
+                partial_credit: true,
+                ..
+            }
+        )
+    }
+
+    pub fn to_json(&self) -> Result<String, TriviaError> {
+        serde_json::to_string(self)
+            .map_err(|e| TriviaError::InvalidInput(format!("answer cannot be saved: {}", e)))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, TriviaError> {
+        serde_json::from_str(json)
+            .map_err(|e| TriviaError::InvalidInput(format!("stored answer is unreadable: {}", e)))
+    }
+}
+
+impl fmt::Display for AnswerSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswerSpec::TrueFalse(answer) => write!(f, "{}", answer),
+            AnswerSpec::MultipleChoice {
+                choices,
+                correct_choice,
+            } => {
+                let shown: Vec<String> = choices
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        let mark = if i == *correct_choice {
+                            " (correct)"
+                        } else {
+                            ""
+                        };
+                        format!("{}. {}{}", choice_label(i), c, mark)
+                    })
+                    .collect();
+                write!(f, "{}", shown.join(", "))
+            }
+            AnswerSpec::FreeText {
+                accepted,
+                max_distance,
+            } => write!(
+                f,
+                "{} (typos forgiven: {})",
+                accepted.join(" / "),
+                max_distance
+            ),
+            AnswerSpec::Numeric {
+                target_value,
+                tolerance,
+                tolerance_percent,
+                unit,
+            } => NumericAnswer {
+                question_id: 0,
+                target_value: *target_value,
+                tolerance: *tolerance,
+                tolerance_percent: *tolerance_percent,
+                unit: unit.clone(),
+            }
+            .fmt(f),
+            AnswerSpec::Ordering {
+                items,
+                partial_credit,
+            } => {
+                let credit = if *partial_credit {
+                    "partial credit"
+                } else {
+                    "all or nothing"
+                };
+                write!(f, "{} ({})", items.join(" → "), credit)
+            }
+        }
+    }
+}
+
+/// A question as it was before one edit, kept in `question_revisions`.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct QuestionRevision {
+    pub revision_id: i32,
+    pub question_id: i32,
+    pub question_text: String,
+    /// JSON-encoded [`AnswerSpec`].
+    pub answer: String,
+    pub explanation: Option<String>,
+    pub source: Option<String>,
+    pub edited_by: Option<i32>,
+    /// Username of `edited_by`, if that admin still exists.
+    pub editor: Option<String>,
+    pub edited_at: Option<chrono::DateTime<Utc>>,
+}
+
+/// A question with how widely it is used, for the question bank screen.
+#[derive(Debug, FromRow, Clone)]
+pub struct QuestionUsage {
+    pub question_id: i32,
+    pub question_text: String,
+    pub kind: QuestionKind,
+    pub difficulty: Difficulty,
+    /// Number of games that include the question.
+    pub game_count: i64,
+    /// Number of recorded answers to the question across all sessions.
+    pub answer_count: i64,
+}
+
+/// A question with everything the admin entered for it, so it can be stored
+/// in one go and a bad field leaves nothing half-made behind.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewQuestion {
+    pub text: String,
+    pub answer: AnswerSpec,
+    pub difficulty: Difficulty,
+    /// `None` uses the configured default in timed mode.
+    pub time_limit_secs: Option<i32>,
+    pub explanation: Option<String>,
+    pub source: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// Filters for searching the question bank. Fields left as `None` match
+/// every question.
+#[derive(Debug, Default, Clone)]
+pub struct QuestionSearch {
+    /// Words that must all start a word of the question text or explanation.
+    pub keyword: Option<String>,
+    pub tag: Option<String>,
+    /// Username of the admin who created the question.
+    pub author: Option<String>,
+    pub kind: Option<QuestionKind>,
+    /// Earliest creation date to include.
+    pub created_from: Option<NaiveDate>,
+    /// Latest creation date to include.
+    pub created_to: Option<NaiveDate>,
+}
+
+impl QuestionSearch {
+    pub fn is_empty(&self) -> bool {
+        self.keyword.is_none()
+            && self.tag.is_none()
+            && self.author.is_none()
+            && self.kind.is_none()
+            && self.created_from.is_none()
+            && self.created_to.is_none()
+    }
+}
+
 #[derive(Debug, FromRow)]
 pub struct GameQuestion {
     pub game_id: i32,
     pub question_id: i32,
     pub question_order: i32,
 }
+
+/// One play-through of a game by a player. `ended_at` stays `None` until the
+/// last question has been answered.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct GameSession {
+    pub session_id: i32,
+    pub player_id: i32,
+    pub game_id: i32,
+    pub mode: GameMode,
+    /// Seed passed to [`PlayOrder::arrange`], or `None` for a fixed order.
+    pub seed: Option<i64>,
+    /// The game's play order when the session started.
+    pub play_order: PlayOrder,
+    pub draw_count: Option<i32>,
+    pub started_at: chrono::DateTime<Utc>,
+    pub ended_at: Option<chrono::DateTime<Utc>>,
+    pub correct_answers: i32,
+    pub total_questions: i32,
+    /// Points earned: each answer's score times its question's
+    /// [`Difficulty::points`].
+    pub score: f64,
+    /// Points available had every answer been right.
+    pub max_score: f64,
+    /// Timed-mode speed bonuses, on top of `score`.
+    pub bonus: f64,
+}
+
+impl GameSession {
+    /// Puts a game's questions, given in their fixed order, in the order this
+    /// session played them.
+    pub fn arrange<T>(&self, questions: &mut Vec<T>) {
+        if let Some(seed) = self.seed {
+            self.play_order
+                .arrange(questions, self.draw_count, seed as u64);
+        }
+    }
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct SessionAnswer {
+    pub answer_id: i32,
+    pub session_id: i32,
+    pub question_id: i32,
+    pub submitted_answer: String,
+    pub is_correct: bool,
+    /// Share of the question's points earned, from 0.0 to 1.0.
+    pub score: f64,
+    pub response_time_ms: i32,
+    pub answered_at: Option<chrono::DateTime<Utc>>,
+}
+
+/// One finished survival run: questions until the player runs out of lives.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct SurvivalRun {
+    pub run_id: i32,
+    pub player_id: i32,
+    /// `None` for a run through the shuffled pool of every game.
+    pub game_id: Option<i32>,
+    /// Wrong answers the player could give before the run ended.
+    pub lives: i32,
+    pub questions_answered: i32,
+    pub correct_answers: i32,
+    /// Points earned, weighted by difficulty like a regular game.
+    pub score: f64,
+    pub played_at: chrono::DateTime<Utc>,
+}
+
+/// A survival high-score row: a player's best run, ranked by correct answers
+/// and then points.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct SurvivalScore {
+    pub rank: i64,
+    pub player_id: i32,
+    pub username: String,
+    pub correct_answers: i32,
+    pub questions_answered: i32,
+    pub score: f64,
+    pub played_at: chrono::DateTime<Utc>,
+}
+
+/// Ease factor a practice card starts with.
+pub const INITIAL_EASE_FACTOR: f64 = 2.5;
+/// SM-2 never lets a card's ease factor fall below this, so even the hardest
+/// questions keep being spaced out a little further each time.
+pub const MIN_EASE_FACTOR: f64 = 1.3;
+/// Correct practice answers given within this many seconds count as perfect
+/// recall.
+pub const QUICK_RECALL_SECS: u64 = 10;
+
+/// A player's spaced-repetition schedule for a question they have missed,
+/// updated after each practice answer with the SM-2 algorithm.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct PracticeCard {
+    pub player_id: i32,
+    pub question_id: i32,
+    /// Correct answers in a row since the question was last missed.
+    pub repetitions: i32,
+    /// How much the interval grows after each correct answer.
+    pub ease_factor: f64,
+    pub interval_days: i32,
+    pub due_at: chrono::DateTime<Utc>,
+    pub reviewed_at: chrono::DateTime<Utc>,
+}
+
+impl PracticeCard {
+    /// A card for a missed question the player hasn't practiced yet. It is
+    /// due straight away.
+    pub fn new(player_id: i32, question_id: i32, now: chrono::DateTime<Utc>) -> Self {
+        Self {
+            player_id,
+            question_id,
+            repetitions: 0,
+            ease_factor: INITIAL_EASE_FACTOR,
+            interval_days: 0,
+            due_at: now,
+            reviewed_at: now,
+        }
+    }
+
+    /// Grades a practice answer on SM-2's 0 to 5 scale from the share of
+    /// credit it earned and how long the player took. Anything below 3 counts
+    /// as a miss.
+    pub fn quality(credit: f64, elapsed: std::time::Duration) -> u8 {
+        if credit == 1.0 {
+            if elapsed.as_secs() < QUICK_RECALL_SECS {
+                5
+            } else {
+                4
+            }
+        } else if credit > 0.0 {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Starts the card's repetitions over, keeping its ease factor, for a
+    /// question missed in a game since it was last practiced.
+    pub fn restart(&mut self) {
+        self.repetitions = 0;
+        self.interval_days = 0;
+    }
+
+    /// Reschedules the card after an answer graded `quality`. A miss starts
+    /// the card over with a one-day interval; each correct answer in a row
+    /// waits one day, then six, then the last interval times the ease factor.
+    pub fn review(&mut self, quality: u8, now: chrono::DateTime<Utc>) {
+        let quality = f64::from(quality.min(5));
+        if quality >= 3.0 {
+            self.interval_days = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (f64::from(self.interval_days) * self.ease_factor).round() as i32,
+            };
+            self.repetitions += 1;
+        } else {
+            self.repetitions = 0;
+            self.interval_days = 1;
+        }
+        let lapse = 5.0 - quality;
+        self.ease_factor =
+            (self.ease_factor + 0.1 - lapse * (0.08 + lapse * 0.02)).max(MIN_EASE_FACTOR);
+        self.due_at = now + chrono::Duration::days(i64::from(self.interval_days));
+        self.reviewed_at = now;
+    }
+}
+
+/// Which of a player's finished sessions counts towards a leaderboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardBasis {
+    /// Highest score, ties broken by the faster completion time.
+    Best,
+    /// Most recently finished session.
+    Latest,
+}
+
+/// A leaderboard row. For a per-game board `games_played` is always 1; for the
+/// global board the totals are summed over one session per game.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct LeaderboardEntry {
+    pub rank: i64,
+    pub player_id: i32,
+    pub username: String,
+    pub correct_answers: i64,
+    pub total_questions: i64,
+    /// The board is ranked by `score` plus `bonus`; see [`GameSession`].
+    pub score: f64,
+    pub max_score: f64,
+    pub bonus: f64,
+    /// Sum of the per-answer response times, used to break ties.
+    pub time_ms: i64,
+    pub games_played: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// An ordering question with `count` items, shown in their correct order.
+    fn ordering_key(count: i32, partial_credit: bool) -> AnswerKey {
+        AnswerKey::Ordering {
+            items: (1..=count)
+                .map(|i| OrderingItem {
+                    item_id: i,
+                    question_id: 1,
+                    item_order: i,
+                    item_text: format!("Item {}", i),
+                })
+                .collect(),
+            partial_credit,
+        }
+    }
+
+    #[test]
+    fn parse_permutation_reads_each_number_once() {
+        assert_eq!(parse_permutation("3,1,2", 3), Some(vec![2, 0, 1]));
+        assert_eq!(parse_permutation(" 2 , 3,1 ", 3), Some(vec![1, 2, 0]));
+    }
+
+    #[test]
+    fn parse_permutation_rejects_duplicates_and_missing_numbers() {
+        assert_eq!(parse_permutation("1,1,2", 3), None);
+        assert_eq!(parse_permutation("1,2", 3), None);
+        assert_eq!(parse_permutation("1,2,3,4", 3), None);
+    }
+
+    #[test]
+    fn parse_permutation_rejects_out_of_range_numbers() {
+        assert_eq!(parse_permutation("0,1,2", 3), None);
+        assert_eq!(parse_permutation("1,2,4", 3), None);
+        assert_eq!(parse_permutation("1,-2,3", 3), None);
+    }
+
+    #[test]
+    fn parse_permutation_rejects_stray_separators() {
+        assert_eq!(parse_permutation("1,2,3,", 3), None);
+        assert_eq!(parse_permutation(",1,2,3", 3), None);
+        assert_eq!(parse_permutation("1,,2,3", 3), None);
+        assert_eq!(parse_permutation("1 2 3", 3), None);
+        assert_eq!(parse_permutation("", 3), None);
+    }
+
+    #[test]
+    fn ordering_score_is_all_or_nothing_without_partial_credit() {
+        let key = ordering_key(4, false);
+        assert_eq!(key.score("1,2,3,4"), 1.0);
+        assert_eq!(key.score("2,1,3,4"), 0.0);
+        assert_eq!(key.score("4,3,2,1"), 0.0);
+    }
+
+    #[test]
+    fn ordering_partial_credit_is_the_share_of_pairs_in_order() {
+        let key = ordering_key(4, true);
+        assert_eq!(key.score("1,2,3,4"), 1.0);
+        // One adjacent swap puts 5 of the 6 pairs in order.
+        assert_eq!(key.score("2,1,3,4"), 5.0 / 6.0);
+        assert_eq!(key.score("1,2,4,3"), 5.0 / 6.0);
+        // Moving the last item to the front breaks the 3 pairs it is in.
+        assert_eq!(key.score("4,1,2,3"), 0.5);
+        assert_eq!(key.score("4,3,2,1"), 0.0);
+    }
+
+    #[test]
+    fn unreadable_ordering_responses_score_nothing() {
+        let key = ordering_key(3, true);
+        assert_eq!(key.score("1,1,2"), 0.0);
+        assert_eq!(key.score("1,2,5"), 0.0);
+        assert_eq!(key.score("1,2,3,"), 0.0);
+    }
+
+    fn card() -> PracticeCard {
+        PracticeCard::new(1, 1, Utc::now())
+    }
+
+    #[test]
+    fn practice_quality_rewards_quick_full_answers() {
+        let quick = Duration::from_secs(QUICK_RECALL_SECS - 1);
+        let slow = Duration::from_secs(QUICK_RECALL_SECS);
+        assert_eq!(PracticeCard::quality(1.0, quick), 5);
+        assert_eq!(PracticeCard::quality(1.0, slow), 4);
+        assert_eq!(PracticeCard::quality(0.5, quick), 2);
+        assert_eq!(PracticeCard::quality(0.0, quick), 1);
+    }
+
+    #[test]
+    fn correct_reviews_wait_one_day_then_six_then_grow() {
+        let now = Utc::now();
+        let mut card = card();
+
+        card.review(5, now);
+        assert_eq!((card.repetitions, card.interval_days), (1, 1));
+        assert_eq!(card.due_at, now + chrono::Duration::days(1));
+        card.review(5, now);
+        assert_eq!((card.repetitions, card.interval_days), (2, 6));
+        // Six days times the ease, which each quality-5 answer raised by 0.1.
+        card.review(5, now);
+        assert_eq!(card.interval_days, (6.0 * 2.7_f64).round() as i32);
+        assert_eq!(card.reviewed_at, now);
+    }
+
+    #[test]
+    fn a_missed_review_starts_over_and_lowers_the_ease() {
+        let now = Utc::now();
+        let mut card = card();
+        card.review(5, now);
+        card.review(5, now);
+
+        let ease = card.ease_factor;
+        card.review(1, now);
+        assert_eq!((card.repetitions, card.interval_days), (0, 1));
+        assert!(card.ease_factor < ease);
+
+        for _ in 0..10 {
+            card.review(0, now);
+        }
+        assert_eq!(card.ease_factor, MIN_EASE_FACTOR);
+    }
+
+    #[test]
+    fn a_restarted_card_keeps_its_ease() {
+        let now = Utc::now();
+        let mut card = card();
+        card.review(5, now);
+        card.review(5, now);
+        let ease = card.ease_factor;
+
+        card.restart();
+        assert_eq!(card.ease_factor, ease);
+        card.review(5, now);
+        assert_eq!((card.repetitions, card.interval_days), (1, 1));
+    }
+}
@@ -0,0 +1,92 @@
+use bcrypt::{BcryptError, DEFAULT_COST};
+
+/// Outcome of checking a login attempt against a stored `password_hash`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PasswordCheck {
+    /// The password matched a bcrypt hash.
+    Valid,
+    /// The password matched a legacy plaintext row and should be rehashed.
+    ValidLegacy,
+    Invalid,
+}
+
+pub fn hash_password(password: &str) -> Result<String, BcryptError> {
+    bcrypt::hash(password, DEFAULT_COST)
+}
+
+/// Returns true if the stored value is a bcrypt hash rather than a plaintext
+/// password left over from before hashing was introduced.
+pub fn is_hashed(stored: &str) -> bool {
+    stored.len() == 60 && stored.starts_with("$2")
+}
+
+pub fn verify_password(password: &str, stored: &str) -> PasswordCheck {
+    if is_hashed(stored) {
+        match bcrypt::verify(password, stored) {
+            Ok(true) => PasswordCheck::Valid,
+            _ => PasswordCheck::Invalid,
+        }
+    } else if constant_time_eq(password.as_bytes(), stored.as_bytes()) {
+        PasswordCheck::ValidLegacy
+    } else {
+        PasswordCheck::Invalid
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_hash_verifies_only_its_own_password() {
+        let hash = hash_password("hunter22").unwrap();
+
+        assert!(is_hashed(&hash));
+        assert_ne!(hash, "hunter22");
+        assert_eq!(verify_password("hunter22", &hash), PasswordCheck::Valid);
+        assert_eq!(verify_password("hunter23", &hash), PasswordCheck::Invalid);
+        assert_eq!(verify_password("", &hash), PasswordCheck::Invalid);
+    }
+
+    #[test]
+    fn is_hashed_tells_bcrypt_from_plaintext() {
+        assert!(is_hashed(
+            "$2b$12$KIXQJQ6Qb1bE0p8cJ1z5UeQ1Wc3Jm8b6QZtXq0s7Yk8f9v2m3n4oO"
+        ));
+        assert!(!is_hashed("password123"));
+        assert!(!is_hashed(""));
+        // The right prefix alone is not enough.
+        assert!(!is_hashed("$2b$12$short"));
+        assert!(!is_hashed(&"x".repeat(60)));
+    }
+
+    #[test]
+    fn legacy_plaintext_rows_still_log_in() {
+        assert_eq!(
+            verify_password("password123", "password123"),
+            PasswordCheck::ValidLegacy
+        );
+        assert_eq!(
+            verify_password("password124", "password123"),
+            PasswordCheck::Invalid
+        );
+        assert_eq!(
+            verify_password("Password123", "password123"),
+            PasswordCheck::Invalid
+        );
+    }
+
+    #[test]
+    fn constant_time_eq_compares_length_and_bytes() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(constant_time_eq(b"", b""));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret1"));
+        assert!(!constant_time_eq(b"secret1", b"secret"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+}
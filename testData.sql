@@ -32,16 +32,56 @@ INSERT INTO questions (question_text, correct_answer, created_by, created_at) VA
 ('Neptune is the closest planet to the Sun', false, 3, '2024-01-17 11:02:00'),
 ('The Moon has its own light source', false, 1, '2024-01-18 12:00:00');
 
+-- Insert a multiple-choice question (correct_answer is unused for this kind)
+INSERT INTO questions (question_text, correct_answer, kind, created_by, created_at) VALUES
+('Which planet is the largest in our solar system?', false, 'multiple_choice', 3, '2024-01-18 12:05:00');
+
+INSERT INTO question_choices (question_id, choice_order, choice_text, is_correct) VALUES
+(11, 1, 'Saturn', false),
+(11, 2, 'Jupiter', true),
+(11, 3, 'Neptune', false),
+(11, 4, 'Earth', false);
+
+-- Insert a free-text question that forgives one typo
+INSERT INTO questions (question_text, correct_answer, kind, created_by, created_at) VALUES
+('Who developed the theory of general relativity?', false, 'free_text', 1, '2024-01-18 12:10:00');
+
+INSERT INTO accepted_answers (question_id, answer_text, max_distance) VALUES
+(12, 'Albert Einstein', 1),
+(12, 'Einstein', 1);
+
+-- Insert numeric questions: one exact, one within 5%
+INSERT INTO questions (question_text, correct_answer, kind, created_by, created_at) VALUES
+('In what year did the first crewed Moon landing take place?', false, 'numeric', 2, '2024-01-18 12:15:00'),
+('What is the average distance from the Earth to the Moon?', false, 'numeric', 3, '2024-01-18 12:20:00');
+
+INSERT INTO numeric_answers (question_id, target_value, tolerance, tolerance_percent, unit) VALUES
+(13, 1969, 0, false, NULL),
+(14, 384400, 5, true, 'km');
+
+-- Insert an ordering question with partial credit
+INSERT INTO questions (question_text, correct_answer, kind, partial_credit, created_by, created_at) VALUES
+('Put these events in chronological order', false, 'ordering', true, 2, '2024-01-18 12:25:00');
+
+INSERT INTO ordering_items (question_id, item_order, item_text) VALUES
+(15, 1, 'Fall of the Western Roman Empire'),
+(15, 2, 'Signing of the Magna Carta'),
+(15, 3, 'Columbus reaches the Americas'),
+(15, 4, 'Storming of the Bastille');
+
 -- Link questions to games
 INSERT INTO game_questions (game_id, question_id, question_order) VALUES
 -- Science Trivia questions
 (1, 1, 1),  -- Earth revolves around Sun
 (1, 2, 2),  -- Water boiling point
 (1, 3, 3),  -- Brain usage myth
+(1, 12, 4), -- General relativity (free text)
 
 -- History Facts questions
 (2, 4, 1),  -- Great Wall visibility
 (2, 5, 2),  -- WWII end date
+(2, 13, 3), -- Moon landing year (numeric)
+(2, 15, 4), -- Historical events (ordering)
 
 -- Tech Quiz questions
 (3, 6, 1),  -- ENIAC
@@ -50,4 +90,21 @@ INSERT INTO game_questions (game_id, question_id, question_order) VALUES
 -- Space Explorer questions
 (4, 8, 1),  -- Mars Red Planet
 (4, 9, 2),  -- Neptune position
-(4, 10, 3); -- Moon light source
+(4, 10, 3), -- Moon light source
+(4, 11, 4), -- Largest planet (multiple choice)
+(4, 14, 5); -- Earth-Moon distance (numeric)
+
+-- Explanations and sources shown after answering
+UPDATE questions
+SET explanation = 'Brain imaging shows activity across virtually the whole brain, even during sleep.',
+    source = 'Scientific American, "Do People Only Use 10 Percent of Their Brains?"'
+WHERE question_id = 3;
+
+UPDATE questions
+SET explanation = 'Apollo 11 landed on 20 July 1969; Neil Armstrong and Buzz Aldrin walked on the Moon.',
+    source = 'NASA, Apollo 11 Mission Overview'
+WHERE question_id = 13;
+
+UPDATE questions
+SET explanation = 'Rome fell in 476, the Magna Carta was sealed in 1215, Columbus arrived in 1492 and the Bastille was stormed in 1789.'
+WHERE question_id = 15;